@@ -0,0 +1,271 @@
+//! Shell completion scripts
+//!
+//! Scripts are generated from [`OPTIONS`] and [`COMMANDS`], the tables the
+//! parser itself uses, so every option, alias and value enumeration that
+//! `parse_args` accepts is offered by the shell.
+
+use crate::cli::error::CliError;
+use crate::cli::options::{OptionSpec, COMMANDS, MODES, OPTIONS, SHELLS};
+
+/// Shells with completion support
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+impl Shell {
+    pub fn parse(name: &str) -> Result<Self, CliError> {
+        match name {
+            "bash" => Ok(Shell::Bash),
+            "zsh" => Ok(Shell::Zsh),
+            "fish" => Ok(Shell::Fish),
+            _ => Err(CliError::InvalidArgument(format!("Unsupported shell: {} (use {})", name, SHELLS.join(", ")))),
+        }
+    }
+}
+
+/// Completion script for `shell`
+pub fn completion_script(shell: Shell) -> String {
+    match shell {
+        Shell::Bash => bash_script(),
+        Shell::Zsh => zsh_script(),
+        Shell::Fish => fish_script(),
+    }
+}
+
+/// Every spelling of an option: `--long`, aliases and `-s`
+fn spellings(option: &OptionSpec) -> Vec<String> {
+    let mut names: Vec<String> = std::iter::once(option.long)
+        .chain(option.aliases.iter().copied())
+        .map(|long| format!("--{}", long))
+        .collect();
+    if let Some(short) = option.short {
+        names.push(format!("-{}", short));
+    }
+    names
+}
+
+fn bash_script() -> String {
+    let all_options: Vec<String> = OPTIONS.iter().flat_map(spellings).collect();
+    let commands: Vec<&str> = COMMANDS.iter().map(|command| command.name).collect();
+    let takes_value: Vec<String> = OPTIONS
+        .iter()
+        .filter(|option| option.value.is_some() && !option.value_optional)
+        .flat_map(spellings)
+        .collect();
+
+    let mut script = String::from(concat!(
+        "# bash completion for rolo (generated by `rolo completions bash`)\n",
+        "_rolo() {\n",
+        "    local cur prev cmd i\n",
+        "    cur=\"${COMP_WORDS[COMP_CWORD]}\"\n",
+        "    prev=\"${COMP_WORDS[COMP_CWORD-1]}\"\n",
+        "\n",
+        "    # --opt=value is split at '=' by COMP_WORDBREAKS\n",
+        "    if [[ \"$cur\" == \"=\" ]]; then\n",
+        "        cur=\"\"\n",
+        "    elif [[ \"$prev\" == \"=\" ]]; then\n",
+        "        prev=\"${COMP_WORDS[COMP_CWORD-2]}\"\n",
+        "    fi\n",
+        "\n",
+        "    case \"$prev\" in\n",
+    ));
+
+    for option in OPTIONS.iter().filter(|option| option.value.is_some()) {
+        let reply = if !option.values.is_empty() {
+            format!("COMPREPLY=($(compgen -W \"{}\" -- \"$cur\"))", option.values.join(" "))
+        } else if option.takes_file() {
+            "COMPREPLY=($(compgen -f -- \"$cur\"))".to_string()
+        } else {
+            "COMPREPLY=()".to_string()
+        };
+        script.push_str(&format!("        {})\n            {}\n            return ;;\n", spellings(option).join("|"), reply));
+    }
+
+    script.push_str(&format!(
+        concat!(
+            "    esac\n",
+            "\n",
+            "    if [[ \"$cur\" == -* ]]; then\n",
+            "        COMPREPLY=($(compgen -W \"{options}\" -- \"$cur\"))\n",
+            "        return\n",
+            "    fi\n",
+            "\n",
+            "    # First word that isn't an option or an option's value\n",
+            "    cmd=\"\"\n",
+            "    for ((i = 1; i < COMP_CWORD; i++)); do\n",
+            "        case \"${{COMP_WORDS[i]}}\" in\n",
+            "            {takes_value}) ((i++)) ;;\n",
+            "            -*) ;;\n",
+            "            *) cmd=\"${{COMP_WORDS[i]}}\"; break ;;\n",
+            "        esac\n",
+            "    done\n",
+            "\n",
+            "    case \"$cmd\" in\n",
+            "        \"\") COMPREPLY=($(compgen -W \"{commands}\" -- \"$cur\")) ;;\n",
+            "        watch|exec) COMPREPLY=($(compgen -W \"{modes}\" -- \"$cur\")) ;;\n",
+        ),
+        options = all_options.join(" "),
+        takes_value = takes_value.join("|"),
+        commands = commands.join(" "),
+        modes = MODES.join(" "),
+    ));
+
+    for command in COMMANDS.iter().filter(|command| !command.arguments.is_empty()) {
+        script.push_str(&format!(
+            "        {}) COMPREPLY=($(compgen -W \"{}\" -- \"$cur\")) ;;\n",
+            command.name,
+            command.arguments.join(" ")
+        ));
+    }
+
+    script.push_str(concat!("    esac\n", "}\n", "complete -F _rolo rolo\n"));
+    script
+}
+
+fn zsh_script() -> String {
+    let mut script = String::from(concat!(
+        "#compdef rolo\n",
+        "# zsh completion for rolo (generated by `rolo completions zsh`)\n",
+        "\n",
+        "_rolo() {\n",
+        "    local -a commands\n",
+        "    commands=(\n",
+    ));
+    for command in COMMANDS {
+        script.push_str(&format!("        '{}:{}'\n", command.name, zsh_quote(&command.help.replace(':', "\\:"))));
+    }
+    script.push_str("    )\n\n    _arguments -s \\\n");
+
+    for option in OPTIONS {
+        let help = zsh_quote(&option.summary().replace('[', "\\[").replace(']', "\\]"));
+
+        // `--opt=` takes `--opt=value` or `--opt value`, `-o+` takes `-ovalue` or `-o value`
+        let (long_suffix, short_suffix, argument) = match option.value {
+            Some(value) if option.value_optional => ("", "", format!("::{}:{}", value, zsh_action(option))),
+            Some(value) => ("=", "+", format!(":{}:{}", value, zsh_action(option))),
+            None => ("", "", String::new()),
+        };
+
+        let mut names: Vec<String> = std::iter::once(option.long)
+            .chain(option.aliases.iter().copied())
+            .map(|long| format!("--{}{}", long, long_suffix))
+            .collect();
+        if let Some(short) = option.short {
+            names.push(format!("-{}{}", short, short_suffix));
+        }
+
+        if names.len() == 1 {
+            script.push_str(&format!("        '{}[{}]{}' \\\n", names[0], help, argument));
+        } else {
+            script.push_str(&format!(
+                "        '({})'{{{}}}'[{}]{}' \\\n",
+                spellings(option).join(" "),
+                names.join(","),
+                help,
+                argument
+            ));
+        }
+    }
+
+    script.push_str(&format!(
+        concat!(
+            "        '1: :->command' \\\n",
+            "        '*:: :->args'\n",
+            "\n",
+            "    case $state in\n",
+            "        command) _describe 'command' commands ;;\n",
+            "        args)\n",
+            "            case $words[1] in\n",
+            "                watch|exec) _values 'mode' {modes} ;;\n",
+        ),
+        modes = MODES.join(" "),
+    ));
+    for command in COMMANDS.iter().filter(|command| !command.arguments.is_empty()) {
+        script.push_str(&format!(
+            "                {}) _values '{}' {} ;;\n",
+            command.name,
+            command.name,
+            command.arguments.join(" ")
+        ));
+    }
+    script.push_str(concat!(
+        "            esac\n",
+        "            ;;\n",
+        "    esac\n",
+        "}\n",
+        "\n",
+        "_rolo \"$@\"\n",
+    ));
+    script
+}
+
+fn fish_script() -> String {
+    let mut script = String::from(concat!(
+        "# fish completion for rolo (generated by `rolo completions fish`)\n",
+        "complete -c rolo -f\n",
+    ));
+
+    for command in COMMANDS {
+        script.push_str(&format!(
+            "complete -c rolo -n '__fish_use_subcommand' -a {} -d {}\n",
+            command.name,
+            fish_quote(command.help)
+        ));
+    }
+    script.push_str(&format!(
+        "complete -c rolo -n '__fish_seen_subcommand_from watch exec; and not __fish_seen_subcommand_from {}' -a '{}'\n",
+        MODES.join(" "),
+        MODES.join(" ")
+    ));
+    for command in COMMANDS.iter().filter(|command| !command.arguments.is_empty()) {
+        script.push_str(&format!(
+            "complete -c rolo -n '__fish_seen_subcommand_from {}' -a '{}'\n",
+            command.name,
+            command.arguments.join(" ")
+        ));
+    }
+
+    for option in OPTIONS {
+        let mut line = String::from("complete -c rolo");
+        if let Some(short) = option.short {
+            line.push_str(&format!(" -s {}", short));
+        }
+        for long in std::iter::once(option.long).chain(option.aliases.iter().copied()) {
+            line.push_str(&format!(" -l {}", long));
+        }
+        match option.value {
+            Some(_) if option.takes_file() => line.push_str(" -F"),
+            Some(_) if !option.values.is_empty() => line.push_str(&format!(" -x -a '{}'", option.values.join(" "))),
+            Some(_) => line.push_str(" -x"),
+            None => {}
+        }
+        line.push_str(&format!(" -d {}\n", fish_quote(&option.summary())));
+        script.push_str(&line);
+    }
+
+    script
+}
+
+/// Completion action for an option's value in an `_arguments` spec
+fn zsh_action(option: &OptionSpec) -> String {
+    if !option.values.is_empty() {
+        format!("({})", option.values.join(" "))
+    } else if option.takes_file() {
+        "_files".to_string()
+    } else {
+        " ".to_string()
+    }
+}
+
+/// Single-quote escaping for zsh (`'` inside a quoted word)
+fn zsh_quote(text: &str) -> String {
+    text.replace('\'', "'\\''")
+}
+
+/// Single-quoted fish string
+fn fish_quote(text: &str) -> String {
+    format!("'{}'", text.replace('\\', "\\\\").replace('\'', "\\'"))
+}
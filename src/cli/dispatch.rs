@@ -1,78 +1,243 @@
 //! CLI dispatch system with RSB patterns
-
-use crate::cli::{CliConfig, parse_args, execute_cli};
-use crate::cli::error::CliError;
+//!
+//! [`run_cli`] is the embeddable entry point: it parses arguments with the
+//! same parser the `rolo` binary uses and returns an exit code instead of
+//! exiting, so other tools can host rolo's CLI. Options are layered over the
+//! user and project config files exactly as for the binary.
+
+use crate::cli::{CliConfig, CliMode, execute_cli, execute_exec, execute_watch, requested_error_format};
+use crate::config::{format_config_show, resolve_config, user_config_path, write_starter_config, ResolvedConfig};
+use crate::cli::completions::{completion_script, Shell};
+use crate::cli::error::{CliError, ErrorFormat, ErrorKind};
+use crate::cli::helpers::json_string;
+use crate::cli::manpage::man_page;
+use crate::cli::helpers::{command_help, environment_summary, help_text, verbose_version_text, version_text};
+use crate::cli::options::find_command;
+use crate::stream::{OutputWriter, StreamConfig};
 use std::process;
 
 /// Main dispatch function following RSB patterns
 pub fn dispatch_cli() -> ! {
     let args: Vec<String> = std::env::args().collect();
+    process::exit(run_cli(&args));
+}
 
-    // Parse arguments
-    let config = match parse_args(&args) {
-        Ok(config) => config,
-        Err(e) => {
-            eprintln!("Error: {}", e);
-            eprintln!("Use --help for usage information");
-            process::exit(1);
-        }
-    };
+/// Parse `args` (including the program name) and run the requested command
+///
+/// Returns the process exit code, which depends on the class of error (see
+/// [`ErrorKind`]); errors are reported on stderr in the `--error-format`.
+pub fn run_cli(args: &[String]) -> i32 {
+    match resolve_config(args) {
+        Ok(resolved) => run_resolved(&resolved),
+        Err(e) => report_error(&e, None, requested_error_format(args)),
+    }
+}
+
+/// Run the command described by a configuration resolved over config files
+pub fn run_resolved(resolved: &ResolvedConfig) -> i32 {
+    match resolved.config.subcommand.as_deref() {
+        Some("config") => run_config_command(resolved),
+        _ => run_with_config(&resolved.config),
+    }
+}
 
-    // Execute CLI action
-    if let Err(e) = execute_cli(&config) {
-        eprintln!("Execution error: {}", e);
-        process::exit(1);
+/// Run the command described by an already parsed configuration
+pub fn run_with_config(config: &CliConfig) -> i32 {
+    if config.help {
+        return match config.subcommand.as_deref() {
+            Some(command) if command != "help" => print_command_help(command),
+            _ => print_help(),
+        };
+    }
+    if config.version {
+        return print_version_for(config);
     }
 
-    process::exit(0);
+    match config.subcommand.as_deref() {
+        Some("help") => match config.arguments.first() {
+            Some(command) => print_command_help(command),
+            None => print_help(),
+        },
+        Some("version") => print_version_for(config),
+        Some("init") => run_init(config),
+        Some("config") => run_config_command(&ResolvedConfig::from(config.clone())),
+        Some("completions") => run_completions(config),
+        Some("man") => print_text(&man_page()),
+        Some("watch") => match execute_watch(config) {
+            Ok(()) => 0,
+            Err(e) => report_error(&e, Some("Watch"), config.error_format),
+        },
+        Some("exec") => match execute_exec(config) {
+            Ok(code) => code,
+            Err(e) => report_error(&e, Some("Exec"), config.error_format),
+        },
+        _ => match execute_cli(config) {
+            Ok(()) => 0,
+            Err(e) => report_error(&e, Some(mode_name(config.mode)), config.error_format),
+        },
+    }
 }
 
-/// RSB-style command handler function type
-pub type CommandHandler = fn(&CliConfig) -> Result<(), CliError>;
+/// Print the help text, returning the exit code
+pub fn print_help() -> i32 {
+    print_text(&help_text())
+}
 
-/// Execute specific command with configuration
-pub fn execute_command(config: &CliConfig, handler: CommandHandler) -> Result<(), CliError> {
-    handler(config)
+/// Help text for one command, if it exists
+pub fn command_help_text(command: &str) -> Option<String> {
+    find_command(command).map(command_help)
 }
 
-/// Built-in command handlers following RSB patterns
+/// Print help for one command, returning the exit code
+pub fn print_command_help(command: &str) -> i32 {
+    match command_help_text(command) {
+        Some(help) => print_text(&help),
+        None => report_usage_error(&CliError::UnsupportedCommand(command.to_string())),
+    }
+}
 
-pub fn handle_columns(config: &CliConfig) -> Result<(), CliError> {
-    use crate::prelude::*;
+/// Print version information, returning the exit code
+pub fn print_version() -> i32 {
+    print_text(&version_text())
+}
 
-    let cols = config.columns.unwrap_or(2);
-    let input = "Sample input text for column formatting"; // TODO: Read from stdin
+/// Print version information, in detail with `--verbose`
+fn print_version_for(config: &CliConfig) -> i32 {
+    if config.verbose {
+        print_text(&verbose_version_text())
+    } else {
+        print_version()
+    }
+}
 
-    match format_columns(input, cols) {
-        Ok(formatted) => println!("{}", formatted),
-        Err(e) => return Err(CliError::ParseError(format!("Column formatting failed: {}", e))),
+/// Run `rolo config [show]`, returning the exit code
+pub fn run_config_command(resolved: &ResolvedConfig) -> i32 {
+    match resolved.config.arguments.first().map(String::as_str) {
+        None | Some("show") => print_text(&format_config_show(resolved)),
+        Some(action) => report_error(
+            &CliError::UnsupportedCommand(format!("config {}", action)),
+            None,
+            resolved.config.error_format,
+        ),
     }
+}
 
-    Ok(())
+/// Run `rolo init`: write the starter user config and show the environment
+pub fn run_init(config: &CliConfig) -> i32 {
+    let written = match user_config_path() {
+        Some(path) => write_starter_config(&path, config.force).map(|()| path).map_err(CliError::from),
+        None => Err(CliError::ProcessingError("No config directory: set HOME or XDG_CONFIG_HOME".to_string())),
+    };
+
+    match written {
+        Ok(path) => print_text(&format!(
+            "✅ Wrote starter config to {}\n   Edit it, then check the result with 'rolo config show'.\n\n{}",
+            path.display(),
+            environment_summary()
+        )),
+        Err(e) => report_error(&e, None, config.error_format),
+    }
 }
 
-pub fn handle_table(config: &CliConfig) -> Result<(), CliError> {
-    use crate::prelude::*;
+/// Run `rolo completions SHELL`, returning the exit code
+pub fn run_completions(config: &CliConfig) -> i32 {
+    let shell = match config.arguments.first() {
+        Some(name) => Shell::parse(name),
+        None => Err(CliError::MissingArgument("completions requires a shell (bash, zsh, fish)".to_string())),
+    };
+    match shell {
+        Ok(shell) => print_text(&completion_script(shell)),
+        Err(e) => report_error(&e, None, config.error_format),
+    }
+}
 
-    let input = "Sample input text for table formatting"; // TODO: Read from stdin
+/// Report an error on stderr, returning its exit code
+///
+/// `command` names the command that failed, if one ran. A closed output
+/// pipe is not reported at all.
+pub fn report_error(error: &CliError, command: Option<&str>, format: ErrorFormat) -> i32 {
+    let kind = error.kind();
 
-    match format_table(input, "\t") {
-        Ok(formatted) => println!("{}", formatted),
-        Err(e) => return Err(CliError::ParseError(format!("Table formatting failed: {}", e))),
+    // Reader went away (e.g. `| head`): exit quietly like other Unix tools
+    if kind != ErrorKind::BrokenPipe {
+        eprintln!("{}", format_error(error, command, format));
+    }
+    kind.exit_code()
+}
+
+/// Error report as printed by [`report_error`]
+///
+/// Text reports put the hint on its own line; JSON reports are a single
+/// `{"kind", "message", "hint"}` object with a `null` hint when there is none.
+pub fn format_error(error: &CliError, command: Option<&str>, format: ErrorFormat) -> String {
+    let hint = error.hint();
+    match format {
+        ErrorFormat::Text => {
+            let mut report = match command {
+                Some(command) => format!("❌ {} command failed: {}", command, error),
+                None => format!("❌ {}", error),
+            };
+            if let Some(hint) = hint {
+                report.push('\n');
+                report.push_str(&hint);
+            }
+            report
+        }
+        ErrorFormat::Json => format!(
+            "{{\"kind\":{},\"message\":{},\"hint\":{}}}",
+            json_string(error.kind().as_str()),
+            json_string(&error.to_string()),
+            hint.as_deref().map(json_string).unwrap_or_else(|| "null".to_string())
+        ),
     }
+}
+
+/// Report a command-line parsing error as text, returning the exit code
+pub fn report_usage_error(error: &CliError) -> i32 {
+    report_error(error, None, ErrorFormat::Text)
+}
+
+/// Report a failed command as text unless the output pipe closed, returning the exit code
+pub fn report_failure(command: &str, error: &CliError) -> i32 {
+    report_error(error, Some(command), ErrorFormat::Text)
+}
+
+/// RSB-style command handler function type
+pub type CommandHandler = fn(&CliConfig) -> Result<(), CliError>;
+
+/// Execute specific command with configuration
+pub fn execute_command(config: &CliConfig, handler: CommandHandler) -> Result<(), CliError> {
+    handler(config)
+}
+
+// Built-in command handlers following RSB patterns
 
-    Ok(())
+pub fn handle_columns(config: &CliConfig) -> Result<(), CliError> {
+    execute_cli(&CliConfig { mode: CliMode::Columns, ..config.clone() })
 }
 
-pub fn handle_list(_config: &CliConfig) -> Result<(), CliError> {
-    use crate::prelude::*;
+pub fn handle_table(config: &CliConfig) -> Result<(), CliError> {
+    execute_cli(&CliConfig { mode: CliMode::Table, ..config.clone() })
+}
 
-    let input = "Sample input text for list formatting"; // TODO: Read from stdin
+pub fn handle_list(config: &CliConfig) -> Result<(), CliError> {
+    execute_cli(&CliConfig { mode: CliMode::List, ..config.clone() })
+}
 
-    match format_list(input) {
-        Ok(formatted) => println!("{}", formatted),
-        Err(e) => return Err(CliError::ParseError(format!("List formatting failed: {}", e))),
+/// Display name of a layout mode for error messages
+fn mode_name(mode: CliMode) -> &'static str {
+    match mode {
+        CliMode::Columns => "Columns",
+        CliMode::Table => "Table",
+        CliMode::List => "List",
     }
+}
 
-    Ok(())
-}
\ No newline at end of file
+/// Write fixed text such as help through the shared output writer
+fn print_text(text: &str) -> i32 {
+    let mut out = OutputWriter::stdout(&StreamConfig::default());
+    match out.write_str(text).and_then(|_| out.flush()) {
+        Ok(()) => 0,
+        Err(e) => report_failure("Output", &e.into()),
+    }
+}
@@ -1,7 +1,23 @@
 //! CLI error types per MODULE_SPEC
 
+use crate::config::error::ConfigError;
+use crate::format::error::FormatError;
+use crate::layout::error::LayoutError;
+use crate::stream::{is_broken_pipe, StreamError, BROKEN_PIPE_EXIT_CODE};
 use std::fmt;
 
+/// Exit code for invalid options or arguments (`EX_USAGE`)
+pub const EXIT_USAGE: i32 = 64;
+
+/// Exit code when the input can't be laid out as requested (`EX_DATAERR`)
+pub const EXIT_LAYOUT: i32 = 65;
+
+/// Exit code for failures reading input, writing output or running commands (`EX_IOERR`)
+pub const EXIT_IO: i32 = 74;
+
+/// Exit code for unreadable or invalid config files (`EX_CONFIG`)
+pub const EXIT_CONFIG: i32 = 78;
+
 #[derive(Debug, Clone)]
 pub enum CliError {
     /// Invalid command line argument
@@ -16,6 +32,16 @@ pub enum CliError {
     ParseError(String),
     /// Error during input processing
     ProcessingError(String),
+    /// Layout failed for the given input and options
+    Layout(LayoutError),
+    /// Layout can't fit the options given (problem, suggestion worked out from the input)
+    Unfit(String, String),
+    /// Reading input, writing output or running a command failed
+    Stream(StreamError),
+    /// Config file could not be loaded or applied
+    Config(ConfigError),
+    /// Structured input could not be parsed
+    Format(FormatError),
 }
 
 impl fmt::Display for CliError {
@@ -27,8 +53,138 @@ impl fmt::Display for CliError {
             CliError::UnsupportedCommand(cmd) => write!(f, "Unsupported command: {}", cmd),
             CliError::ParseError(msg) => write!(f, "Parse error: {}", msg),
             CliError::ProcessingError(msg) => write!(f, "Processing error: {}", msg),
+            CliError::Layout(err) => write!(f, "{}", err),
+            CliError::Unfit(problem, _) => write!(f, "{}", problem),
+            CliError::Stream(err) => write!(f, "{}", err),
+            CliError::Config(err) => write!(f, "{}", err),
+            CliError::Format(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for CliError {}
+
+impl From<LayoutError> for CliError {
+    fn from(err: LayoutError) -> Self {
+        CliError::Layout(err)
+    }
+}
+
+impl From<StreamError> for CliError {
+    fn from(err: StreamError) -> Self {
+        CliError::Stream(err)
+    }
+}
+
+impl From<ConfigError> for CliError {
+    fn from(err: ConfigError) -> Self {
+        CliError::Config(err)
+    }
+}
+
+impl From<FormatError> for CliError {
+    fn from(err: FormatError) -> Self {
+        CliError::Format(err)
+    }
+}
+
+/// Class of a failure, deciding the exit code
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ErrorKind {
+    Usage,
+    Config,
+    Io,
+    Layout,
+    BrokenPipe,
+}
+
+impl ErrorKind {
+    /// Name used in `--error-format json` output
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorKind::Usage => "usage",
+            ErrorKind::Config => "config",
+            ErrorKind::Io => "io",
+            ErrorKind::Layout => "layout",
+            ErrorKind::BrokenPipe => "broken_pipe",
+        }
+    }
+
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            ErrorKind::Usage => EXIT_USAGE,
+            ErrorKind::Config => EXIT_CONFIG,
+            ErrorKind::Io => EXIT_IO,
+            ErrorKind::Layout => EXIT_LAYOUT,
+            ErrorKind::BrokenPipe => BROKEN_PIPE_EXIT_CODE,
         }
     }
 }
 
-impl std::error::Error for CliError {}
\ No newline at end of file
+impl CliError {
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            CliError::InvalidArgument(_)
+            | CliError::MissingArgument(_)
+            | CliError::InvalidWidth(_)
+            | CliError::UnsupportedCommand(_)
+            | CliError::ParseError(_) => ErrorKind::Usage,
+            CliError::ProcessingError(_) => ErrorKind::Io,
+            CliError::Layout(LayoutError::IoError(_)) => ErrorKind::Io,
+            CliError::Layout(_) | CliError::Unfit(_, _) => ErrorKind::Layout,
+            CliError::Stream(err) if is_broken_pipe(err) => ErrorKind::BrokenPipe,
+            CliError::Stream(_) => ErrorKind::Io,
+            CliError::Config(ConfigError::WriteError(_, _)) => ErrorKind::Io,
+            CliError::Config(_) => ErrorKind::Config,
+            CliError::Format(FormatError::Malformed(_, _) | FormatError::DuplicateColumn(_, _)) => ErrorKind::Layout,
+            CliError::Format(_) => ErrorKind::Usage,
+        }
+    }
+
+    pub fn exit_code(&self) -> i32 {
+        self.kind().exit_code()
+    }
+
+    /// What the user can do about the error, if there's a general remedy
+    pub fn hint(&self) -> Option<String> {
+        let hint = match self {
+            CliError::Unfit(_, suggestion) => suggestion.as_str(),
+            CliError::Layout(LayoutError::WidthTooSmall(_, _) | LayoutError::ColumnTooNarrow(_)) => {
+                "Use a larger --width, fewer --cols or a smaller --gap"
+            }
+            CliError::Layout(LayoutError::InvalidColumnCount(_)) => "Use --cols with at least one column",
+            CliError::Stream(StreamError::BinaryInput(_)) => "Pass --binary-ok to lay it out anyway",
+            CliError::Stream(StreamError::InvalidUtf8(_)) => "Use --encoding lossy or --encoding latin1",
+            CliError::Config(ConfigError::UnknownProfile(_, _)) => "Run 'rolo config show' to see the loaded config files",
+            CliError::Config(ConfigError::AlreadyExists(_)) => "Pass --force to overwrite it",
+            CliError::Config(ConfigError::WriteError(_, _)) => "Check that the config directory is writable",
+            CliError::Config(_) => "Fix the config file, or run 'rolo config show' to see where settings come from",
+            CliError::Format(FormatError::Unsupported(_, _)) => "Use --format delimited, or rebuild rolo with the feature enabled",
+            CliError::Format(FormatError::InvalidDelimiter(_, _)) => "Use --format delimited for longer delimiters",
+            CliError::Format(FormatError::UnknownField(_, _)) => "Name a column, or a path prefix such as user.details or tags",
+            CliError::Format(FormatError::DuplicateColumn(_, _)) => "Use --max-depth to keep nested values as JSON cells",
+            _ if self.kind() == ErrorKind::Usage => "Use 'rolo help' for usage information",
+            _ => return None,
+        };
+        Some(hint.to_string())
+    }
+}
+
+/// How errors are reported on stderr (`--error-format`)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ErrorFormat {
+    /// A message for people, with the hint on its own line
+    Text,
+    /// One `{"kind", "message", "hint"}` object per error
+    Json,
+}
+
+impl ErrorFormat {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "text" => Some(ErrorFormat::Text),
+            "json" => Some(ErrorFormat::Json),
+            _ => None,
+        }
+    }
+}
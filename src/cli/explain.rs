@@ -0,0 +1,271 @@
+//! `--explain`: how a layout was sized
+//!
+//! The explanation is worked out with the same functions that draw the
+//! layout ([`plan_table`], [`column_width`], [`split_column_items`]) and is
+//! written to stderr before the layout itself, so it still shows up when the
+//! layout fails, e.g. because the width is too small.
+
+use crate::cli::helpers::{json_string, unfit_columns};
+use crate::cli::utils::{table_model, CliConfig, CliMode};
+use crate::config::ValueSource;
+use crate::layout::{column_width, plan_table, split_column_items, LayoutConfig, TablePlan};
+use crate::width::get_terminal_width_with_source;
+
+/// Output format of `--explain`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExplainFormat {
+    Text,
+    Json,
+}
+
+impl ExplainFormat {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "text" => Some(ExplainFormat::Text),
+            "json" => Some(ExplainFormat::Json),
+            _ => None,
+        }
+    }
+}
+
+/// How the configured layout sizes a given input
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayoutExplanation {
+    pub mode: CliMode,
+    pub width: usize,
+    /// Where the width came from, e.g. `--width`, `env ROLO_WIDTH` or `terminal: COLUMNS`
+    pub width_source: String,
+    /// Delimiter splitting the input; `None` when input is split into lines or is JSON
+    pub delimiter: Option<String>,
+    pub delimiter_source: &'static str,
+    /// Table input format after `auto` is resolved (`delimited`, `csv`, `json` or `jsonl`)
+    pub format: Option<&'static str>,
+    /// Table data rows (without the header), or rows of items
+    pub rows: usize,
+    /// The table's first row is a header
+    pub header: bool,
+    pub columns: usize,
+    /// Items laid out by columns and list modes
+    pub items: usize,
+    /// Column sizing in table mode
+    pub table: Option<TablePlan>,
+    /// Column width in columns mode
+    pub column_width: Option<usize>,
+    /// Why the layout can't be drawn, if it can't
+    pub problem: Option<String>,
+}
+
+/// Explain how `config` lays out `input` (after any `--pre` filters)
+pub fn explain_layout(config: &CliConfig, input: &str) -> LayoutExplanation {
+    let width = config.layout_width();
+    let width_source = match (config.width, config.fit_mode) {
+        (Some(_), _) => match &config.width_source {
+            ValueSource::Cli => "--width".to_string(),
+            source => source.to_string(),
+        },
+        (None, true) => format!("terminal: {}", get_terminal_width_with_source().1),
+        (None, false) => "fixed (--no-fit)".to_string(),
+    };
+
+    let mut explanation = LayoutExplanation {
+        mode: config.mode,
+        width,
+        width_source,
+        delimiter: None,
+        delimiter_source: "lines",
+        format: None,
+        rows: 0,
+        header: false,
+        columns: 0,
+        items: 0,
+        table: None,
+        column_width: None,
+        problem: None,
+    };
+
+    if config.null_data {
+        explanation.delimiter = Some("\0".to_string());
+        explanation.delimiter_source = "--null-data";
+    } else if let Some(delimiter) = &config.delimiter {
+        explanation.delimiter = Some(delimiter.clone());
+        explanation.delimiter_source = "--delim";
+    }
+
+    match config.mode {
+        CliMode::Table => {
+            let delimiter = config.table_delimiter();
+            if config.input_format.is_json() {
+                explanation.delimiter = None;
+                explanation.delimiter_source = "object keys";
+            } else if !config.null_data && config.delimiter.is_none() {
+                explanation.delimiter = Some(delimiter.to_string());
+                explanation.delimiter_source = "default";
+            }
+
+            explanation.format = Some(config.input_format.resolve(delimiter).as_str());
+
+            match table_model(config, input) {
+                Ok(model) => {
+                    explanation.rows = model.rows.len();
+                    explanation.header = model.headers.is_some();
+                    explanation.columns = model.column_count();
+                    explanation.table = Some(plan_table(&model, width));
+                }
+                Err(e) => explanation.problem = Some(e.to_string()),
+            }
+        }
+        CliMode::Columns => {
+            let cols = config.columns.unwrap_or(2);
+            let layout_config = LayoutConfig { width, gap: config.gap.unwrap_or(2), padding: 1 };
+
+            let items = split_column_items(input, config.item_delimiter());
+            explanation.items = items.len();
+            explanation.columns = cols;
+            explanation.rows = explanation.items.div_ceil(cols);
+            match column_width(cols, &layout_config) {
+                Ok(col_width) => explanation.column_width = Some(col_width),
+                Err(_) => {
+                    let unfit = unfit_columns(&items, cols, layout_config.gap, width);
+                    explanation.problem = Some(format!("{}. {}", unfit, unfit.hint().unwrap_or_default()));
+                }
+            }
+        }
+        CliMode::List => {
+            explanation.items = split_column_items(input, config.item_delimiter()).len();
+            explanation.columns = 1;
+            explanation.rows = explanation.items;
+        }
+    }
+
+    explanation
+}
+
+impl LayoutExplanation {
+    pub fn render(&self, format: ExplainFormat) -> String {
+        match format {
+            ExplainFormat::Text => self.to_text(),
+            ExplainFormat::Json => self.to_json(),
+        }
+    }
+
+    /// Human-readable report, one fact per line
+    pub fn to_text(&self) -> String {
+        let mut lines = vec![
+            format!("Layout: {}", self.mode.as_str()),
+            format!("Width: {} ({})", self.width, self.width_source),
+            match &self.delimiter {
+                Some(delimiter) => format!("Delimiter: {:?} ({})", delimiter, self.delimiter_source),
+                None if self.format.is_some() => format!("Delimiter: none, cells from {}", self.delimiter_source),
+                None => "Delimiter: none, one item per line".to_string(),
+            },
+        ];
+        if let Some(format) = self.format {
+            lines.push(format!("Format: {}", format));
+        }
+
+        match &self.table {
+            Some(plan) => {
+                lines.push(format!("Rows: {}{}", self.rows, if self.header { " plus header" } else { "" }));
+                lines.push(format!("Columns: {}", self.columns));
+                lines.push(format!("Required width: {} (natural widths plus separators)", plan.required_width));
+                match plan.scale_factor {
+                    Some(scale) => lines.push(format!("Scaling: columns scaled by {:.2} to fit {}", scale, self.width)),
+                    None => lines.push("Scaling: none, the table fits".to_string()),
+                }
+                lines.push("  column  natural  final".to_string());
+                for (i, (natural, width)) in plan.natural_widths.iter().zip(&plan.widths).enumerate() {
+                    let change = if natural == width { String::new() } else { format!("  ({:+})", *width as i64 - *natural as i64) };
+                    lines.push(format!("  {:>6}  {:>7}  {:>5}{}", i + 1, natural, width, change));
+                }
+                if plan.truncated.is_empty() {
+                    lines.push("Truncated cells: none".to_string());
+                } else {
+                    lines.push(format!("Truncated cells: {}", plan.truncated.len()));
+                    for cell in &plan.truncated {
+                        let header = if self.header && cell.row == 0 { " (header)" } else { "" };
+                        lines.push(format!(
+                            "  row {}{}, column {}: {} -> {}",
+                            cell.row + 1,
+                            header,
+                            cell.column + 1,
+                            cell.width,
+                            plan.widths[cell.column]
+                        ));
+                    }
+                }
+            }
+            None => {
+                lines.push(format!("Items: {}", self.items));
+                lines.push(format!("Rows: {}", self.rows));
+                lines.push(format!("Columns: {}", self.columns));
+                if let Some(col_width) = self.column_width {
+                    lines.push(format!("Column width: {}", col_width));
+                }
+            }
+        }
+
+        if let Some(problem) = &self.problem {
+            lines.push(format!("Problem: {}", problem));
+        }
+        lines.join("\n")
+    }
+
+    /// The report as a single JSON object
+    pub fn to_json(&self) -> String {
+        let mut fields = vec![
+            format!("\"mode\":{}", json_string(self.mode.as_str())),
+            format!("\"width\":{}", self.width),
+            format!("\"width_source\":{}", json_string(&self.width_source)),
+            format!("\"delimiter\":{}", self.delimiter.as_deref().map(json_string).unwrap_or_else(|| "null".to_string())),
+            format!("\"delimiter_source\":{}", json_string(self.delimiter_source)),
+            format!("\"format\":{}", self.format.map(json_string).unwrap_or_else(|| "null".to_string())),
+            format!("\"rows\":{}", self.rows),
+            format!("\"header\":{}", self.header),
+            format!("\"columns\":{}", self.columns),
+        ];
+
+        match &self.table {
+            Some(plan) => {
+                fields.push(format!("\"required_width\":{}", plan.required_width));
+                fields.push(format!(
+                    "\"scale_factor\":{}",
+                    plan.scale_factor.map(|scale| format!("{:.4}", scale)).unwrap_or_else(|| "null".to_string())
+                ));
+                let widths: Vec<String> = plan
+                    .natural_widths
+                    .iter()
+                    .zip(&plan.widths)
+                    .enumerate()
+                    .map(|(i, (natural, width))| format!("{{\"column\":{},\"natural\":{},\"final\":{}}}", i + 1, natural, width))
+                    .collect();
+                fields.push(format!("\"column_widths\":[{}]", widths.join(",")));
+                let truncated: Vec<String> = plan
+                    .truncated
+                    .iter()
+                    .map(|cell| {
+                        format!(
+                            "{{\"row\":{},\"header\":{},\"column\":{},\"width\":{},\"final\":{}}}",
+                            cell.row + 1,
+                            self.header && cell.row == 0,
+                            cell.column + 1,
+                            cell.width,
+                            plan.widths[cell.column]
+                        )
+                    })
+                    .collect();
+                fields.push(format!("\"truncated\":[{}]", truncated.join(",")));
+            }
+            None => {
+                fields.push(format!("\"items\":{}", self.items));
+                if let Some(col_width) = self.column_width {
+                    fields.push(format!("\"column_width\":{}", col_width));
+                }
+            }
+        }
+
+        if let Some(problem) = &self.problem {
+            fields.push(format!("\"problem\":{}", json_string(problem)));
+        }
+        format!("{{{}}}", fields.join(","))
+    }
+}
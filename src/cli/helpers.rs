@@ -1,47 +1,323 @@
 //! CLI internal helpers per MODULE_SPEC
 
-use crate::cli::error::CliError;
+use crate::cli::error::{CliError, EXIT_CONFIG, EXIT_IO, EXIT_LAYOUT, EXIT_USAGE};
+use crate::cli::options::{CommandSpec, OptionSpec, COMMANDS, EXAMPLES, MODES, OPTIONS};
+use crate::config::{ENV_VARS, NO_ENV_OPTIONS, PROFILE_ENV_VAR};
+use crate::layout::{guess_delimiter, ListAlignment, MIN_COLUMN_WIDTH};
+use crate::width::{get_display_width, get_terminal_width_with_source, MAX_WIDTH, WIDTH_STRATEGY};
+use crate::FEATURES;
+use std::io::IsTerminal;
+use crate::stream::BROKEN_PIPE_EXIT_CODE;
+
+/// Largest `--cols` value
+pub(crate) const MAX_COLUMNS: usize = 10;
+
+/// Largest `--gap` value
+pub(crate) const MAX_GAP: usize = 20;
+
+/// List styles understood by the list layout
+pub(crate) const LIST_STYLES: &[&str] = &["numbers", "bullets", "stars", "dots", "dash"];
 
 /// Parse column count from argument
 pub(crate) fn parse_column_count(arg: &str) -> Result<usize, CliError> {
     match arg.parse::<usize>() {
-        Ok(n) if n > 0 && n <= 10 => Ok(n),
-        Ok(n) => Err(CliError::InvalidArgument(format!("Column count {} out of range (1-10)", n))),
+        Ok(n) if (1..=MAX_COLUMNS).contains(&n) => Ok(n),
+        Ok(n) => Err(CliError::InvalidArgument(format!("Column count {} out of range (1-{})", n, MAX_COLUMNS))),
         Err(_) => Err(CliError::InvalidArgument(format!("Column count must be a number: {}", arg))),
     }
 }
 
-/// Show version information
-pub(crate) fn show_version() {
-    println!("rolo v{}", env!("CARGO_PKG_VERSION"));
-    println!("The spiritual love child of pr, paste, and col");
-    println!("Built with RSB Framework");
-}
-
-/// Show help message
-pub(crate) fn show_help() {
-    println!("rolo v{} - Text layout tool for Unix pipelines", env!("CARGO_PKG_VERSION"));
-    println!("The spiritual love child of pr, paste, and col");
-    println!();
-    println!("USAGE:");
-    println!("    rolo [OPTIONS] [SUBCOMMAND]");
-    println!();
-    println!("OPTIONS:");
-    println!("    --cols N        Format text in N columns (1-10)");
-    println!("    --width N       Set terminal width (10-200)");
-    println!("    --help, -h      Show this help message");
-    println!("    --version, -V   Show version information");
-    println!();
-    println!("SUBCOMMANDS:");
-    println!("    table           Format as table with headers");
-    println!("    list            Format as bulleted list");
-    println!("    columns         Format in columns (default)");
-    println!();
-    println!("EXAMPLES:");
-    println!("    printf '%s\\n' $LIST | rolo --cols 4");
-    println!("    cat data.tsv | rolo table");
-    println!("    ls -la | rolo --cols 3 --width 120");
-    println!();
-    println!("PIPELINE INTEGRATION:");
-    println!("    echo \"text\" | jynx | rolo --cols 2 | boxy");
-}
\ No newline at end of file
+/// Parse gap between columns from argument
+pub(crate) fn parse_gap(arg: &str) -> Result<usize, CliError> {
+    match arg.parse::<usize>() {
+        Ok(gap) if gap <= MAX_GAP => Ok(gap),
+        Ok(gap) => Err(CliError::InvalidArgument(format!("Gap too large (max {}): {}", MAX_GAP, gap))),
+        Err(_) => Err(CliError::InvalidArgument(format!("Invalid gap value: {}", arg))),
+    }
+}
+
+/// Parse a comma-separated `--fields` list of column names or paths
+pub(crate) fn parse_fields(arg: &str) -> Result<Vec<String>, CliError> {
+    let fields: Vec<String> = arg.split(',').map(str::trim).filter(|field| !field.is_empty()).map(String::from).collect();
+    if fields.is_empty() {
+        return Err(CliError::InvalidArgument(format!("Invalid fields: {:?} (use e.g. name,user.city)", arg)));
+    }
+    Ok(fields)
+}
+
+/// Parse list alignment from argument
+pub(crate) fn parse_alignment(arg: &str) -> Result<ListAlignment, CliError> {
+    match arg.to_lowercase().as_str() {
+        "left" | "l" => Ok(ListAlignment::Left),
+        "right" | "r" => Ok(ListAlignment::Right),
+        "center" | "c" => Ok(ListAlignment::Center),
+        _ => Err(CliError::InvalidArgument(format!("Invalid alignment: {}. Use left, right, or center", arg))),
+    }
+}
+
+/// Parse list style from argument
+pub(crate) fn parse_list_style(arg: &str) -> Result<String, CliError> {
+    if LIST_STYLES.contains(&arg) {
+        Ok(arg.to_string())
+    } else {
+        Err(CliError::InvalidArgument(format!("Invalid list style: {}. Use {}", arg, LIST_STYLES.join(", "))))
+    }
+}
+
+/// Version information
+pub(crate) fn version_text() -> String {
+    format!("rolo {}\n", env!("CARGO_PKG_VERSION"))
+}
+
+/// Version information for bug reports: build features, width strategy and platform
+pub(crate) fn verbose_version_text() -> String {
+    format!(
+        "{}Features:        {}\nWidth strategy:  {}\nPlatform:        {}-{} ({} build)\n",
+        version_text(),
+        feature_list(),
+        WIDTH_STRATEGY,
+        std::env::consts::ARCH,
+        std::env::consts::OS,
+        if cfg!(debug_assertions) { "debug" } else { "release" }
+    )
+}
+
+/// Enabled cargo features, comma-separated
+fn feature_list() -> String {
+    if FEATURES.is_empty() {
+        "none".to_string()
+    } else {
+        FEATURES.join(", ")
+    }
+}
+
+/// Width of the longest option label, so every description starts in one column
+fn option_label_width() -> usize {
+    OPTIONS.iter().map(|option| option.label().chars().count()).max().unwrap_or(0)
+}
+
+/// Width of the longest command name
+fn command_name_width() -> usize {
+    COMMANDS.iter().map(|command| command.name.len()).max().unwrap_or(0)
+}
+
+/// Help message, with commands and options generated from the option tables
+pub(crate) fn help_text() -> String {
+    let mut help = String::from(concat!(
+        "Rolo - Text layout tool for Unix pipelines\n",
+        "\n",
+        "USAGE:\n",
+        "    rolo [COMMAND] [OPTIONS]\n",
+        "\n",
+        "COMMANDS:\n",
+    ));
+    for command in COMMANDS {
+        help.push_str(&format!("    {:<width$} {}\n", command.name, command.help, width = command_name_width()));
+    }
+
+    help.push_str("\nRun 'rolo help COMMAND' for a command's options and examples.\n\nOPTIONS:\n");
+    for option in OPTIONS {
+        help.push_str(&format!("    {:<width$} {}\n", option.label(), option.summary(), width = option_label_width()));
+    }
+
+    help.push_str(concat!(
+        "\n",
+        "CONFIG FILES:\n",
+        "    ~/.config/rolo/config.toml (or $XDG_CONFIG_HOME/rolo/) and the nearest\n",
+        "    .rolo.toml set defaults for mode, cols, width, gap, delimiter, alignment,\n",
+        "    list_style and more; command line options always win. 'rolo init' writes\n",
+        "    a commented starter file.\n",
+    ));
+    help.push_str("\nENVIRONMENT:\n");
+    let vars: Vec<&str> = ENV_VARS.iter().map(|(var, _)| *var).chain(std::iter::once(PROFILE_ENV_VAR)).collect();
+    let excluded: Vec<String> = NO_ENV_OPTIONS.iter().map(|long| format!("--{}", long)).collect();
+    help.push_str(&wrap_words(
+        &format!(
+            "{} override config files; options override them. {} apply to one run and have no variable.",
+            join_list(&vars),
+            join_list(&excluded)
+        ),
+        "    ",
+        78,
+    ));
+    help.push_str(&format!(
+        "\nEXIT STATUS:\n    0 success, {} usage error, {} layout impossible, {} input/output error,\n    {} config error, {} output pipe closed; exec exits with its command's status.\n",
+        EXIT_USAGE, EXIT_LAYOUT, EXIT_IO, EXIT_CONFIG, BROKEN_PIPE_EXIT_CODE
+    ));
+    help.push_str("\nEXAMPLES:\n");
+    for (_, example) in EXAMPLES {
+        help.push_str(&format!("    {}\n", example));
+    }
+
+    help.push_str(concat!(
+        "\n",
+        "PIPELINE INTEGRATION:\n",
+        "    echo \"text\" | jynx | rolo --cols 2 | boxy\n",
+    ));
+    help
+}
+
+/// `a, b and c`
+fn join_list<S: AsRef<str>>(items: &[S]) -> String {
+    match items {
+        [] => String::new(),
+        [only] => only.as_ref().to_string(),
+        [init @ .., last] => {
+            let init: Vec<&str> = init.iter().map(AsRef::as_ref).collect();
+            format!("{} and {}", init.join(", "), last.as_ref())
+        }
+    }
+}
+
+/// `text` as lines of at most `width` columns, each starting with `indent`
+fn wrap_words(text: &str, indent: &str, width: usize) -> String {
+    let mut wrapped = String::new();
+    let mut line = String::from(indent);
+    for word in text.split_whitespace() {
+        if line.len() > indent.len() && line.len() + 1 + word.len() > width {
+            wrapped.push_str(&line);
+            wrapped.push('\n');
+            line = String::from(indent);
+        }
+        if line.len() > indent.len() {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+    wrapped.push_str(&line);
+    wrapped.push('\n');
+    wrapped
+}
+
+/// Help for one command: usage, the options that affect it and examples
+pub(crate) fn command_help(command: &CommandSpec) -> String {
+    let mut help = format!("rolo {} - {}\n\nUSAGE:\n    {}\n", command.name, command.help, command.usage);
+
+    let specific: Vec<&OptionSpec> = OPTIONS.iter().filter(|option| option.commands.contains(&command.name)).collect();
+    if !specific.is_empty() {
+        help.push_str("\nOPTIONS:\n");
+        for option in specific {
+            help.push_str(&format!("    {:<width$} {}\n", option.label(), option.summary(), width = option_label_width()));
+        }
+    }
+
+    // Layout and streaming commands also take the input/output options
+    if MODES.contains(&command.name) || command.name == "watch" || command.name == "exec" {
+        help.push_str("\nGENERAL OPTIONS:\n");
+        for option in OPTIONS.iter().filter(|option| option.commands.is_empty()) {
+            help.push_str(&format!("    {:<width$} {}\n", option.label(), option.summary(), width = option_label_width()));
+        }
+    }
+    if command.name == "watch" || command.name == "exec" {
+        help.push_str("\nMODE is columns (default), table or list; see 'rolo help MODE' for its options.\n");
+    }
+
+    if !command.examples.is_empty() {
+        help.push_str("\nEXAMPLES:\n");
+        for (description, example) in command.examples {
+            help.push_str(&format!("    # {}\n    {}\n", description, example));
+        }
+    }
+    help
+}
+
+/// `text` as a quoted JSON string
+pub(crate) fn json_string(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Explain why `cols` columns don't fit in `width`, suggesting options that would
+///
+/// Suggestions depend on the items being laid out: no more columns than
+/// items, and a width that fits the widest item when one is in range.
+pub(crate) fn unfit_columns(items: &[&str], cols: usize, gap: usize, width: usize) -> CliError {
+    let needed = |cols: usize, gap: usize| cols * MIN_COLUMN_WIDTH + cols.saturating_sub(1) * gap;
+    let problem = format!("{} columns need at least {} cells at --width {} with --gap {}", cols, needed(cols, gap), width, gap);
+
+    let mut options = Vec::new();
+    if let Some(fits) = (1..cols).rev().find(|&fewer| needed(fewer, gap) <= width) {
+        options.push(format!("--cols {}", fits.min(items.len().max(1))));
+    }
+    if cols > 1 && width >= cols * MIN_COLUMN_WIDTH {
+        options.push(format!("--gap {}", (width - cols * MIN_COLUMN_WIDTH) / (cols - 1)));
+    }
+    let widest = items.iter().map(|item| get_display_width(item).unwrap_or(item.len())).max().unwrap_or(0);
+    let comfortable = cols * widest.max(MIN_COLUMN_WIDTH) + cols.saturating_sub(1) * gap;
+    if comfortable <= MAX_WIDTH {
+        options.push(format!("--width {}", comfortable));
+    } else if needed(cols, gap) <= MAX_WIDTH {
+        options.push(format!("--width {}", needed(cols, gap)));
+    }
+
+    let suggestion = match options.split_last() {
+        Some((last, [])) => format!("Try {}", last),
+        Some((last, rest)) => format!("Try {} or {}", rest.join(", "), last),
+        None => "Use fewer --cols or a smaller --gap".to_string(),
+    };
+    CliError::Unfit(problem, suggestion)
+}
+
+/// Warning for a table delimiter that never appears in the input, naming the likely one
+pub(crate) fn delimiter_warning(input: &str, delimiter: &str) -> Option<String> {
+    if input.trim().is_empty() || delimiter.is_empty() || input.contains(delimiter) {
+        return None;
+    }
+
+    let shown = |delimiter: &str| match delimiter {
+        "\t" => "tab".to_string(),
+        "," => "comma".to_string(),
+        "|" => "pipe".to_string(),
+        ";" => "semicolon".to_string(),
+        other => format!("{:?}", other),
+    };
+    let mut warning = format!("⚠️  Delimiter {} never appears in the input", shown(delimiter));
+    match guess_delimiter(input) {
+        Some(likely) => {
+            let flag = if likely == "\t" { "$'\\t'".to_string() } else { format!("'{}'", likely) };
+            warning.push_str(&format!("; it looks {}-separated, try --delim {}", shown(likely), flag));
+        }
+        None => warning.push_str("; every line becomes a single cell"),
+    }
+    Some(warning)
+}
+
+/// What rolo detects about the terminal and build, as shown by `rolo init`
+pub(crate) fn environment_summary() -> String {
+    let (width, source) = get_terminal_width_with_source();
+    let var = |name: &str| std::env::var(name).ok().filter(|value| !value.is_empty());
+
+    let mut color = vec![if std::io::stdout().is_terminal() { "stdout is a terminal" } else { "stdout is not a terminal" }.to_string()];
+    match var("TERM") {
+        Some(term) => color.push(format!("TERM={}", term)),
+        None => color.push("TERM unset".to_string()),
+    }
+    if let Some(colorterm) = var("COLORTERM") {
+        color.push(format!("COLORTERM={}", colorterm));
+    }
+    if var("NO_COLOR").is_some() {
+        color.push("NO_COLOR set".to_string());
+    }
+
+    format!(
+        "Environment:\n  Terminal width  {} ({})\n  Color           {}\n  Features        {}\n  Width strategy  {}\n",
+        width,
+        source,
+        color.join(", "),
+        feature_list(),
+        WIDTH_STRATEGY
+    )
+}
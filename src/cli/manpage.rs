@@ -0,0 +1,132 @@
+//! rolo(1) man page
+//!
+//! Generated from the option tables and the config module's environment
+//! variables, like the help text, so `rolo man > rolo.1` always documents
+//! exactly what the parser accepts.
+
+use crate::cli::error::{EXIT_CONFIG, EXIT_IO, EXIT_LAYOUT, EXIT_USAGE};
+use crate::cli::options::{OptionAction, OptionSpec, COMMANDS, EXAMPLES, OPTIONS};
+use crate::config::{ENV_VARS, NO_ENV_OPTIONS, PROFILE_ENV_VAR, PROJECT_CONFIG_NAME};
+use crate::stream::BROKEN_PIPE_EXIT_CODE;
+
+/// The man page in roff format
+pub fn man_page() -> String {
+    let version = env!("CARGO_PKG_VERSION");
+    let mut page = format!(".TH ROLO 1 \"\" \"rolo {}\" \"User Commands\"\n", version);
+
+    page.push_str(concat!(
+        ".SH NAME\n",
+        "rolo \\- text layout tool for Unix pipelines\n",
+        ".SH SYNOPSIS\n",
+        ".B rolo\n",
+        "[\\fICOMMAND\\fR] [\\fIOPTIONS\\fR]\n",
+        ".br\n",
+        ".B rolo\n",
+        "\\fBwatch\\fR|\\fBexec\\fR [\\fIMODE\\fR] [\\fIOPTIONS\\fR] \\-\\- \\fICMD\\fR...\n",
+        ".SH DESCRIPTION\n",
+        "\\fBrolo\\fR reads text from standard input and lays it out as columns, a table\n",
+        "or a list that fits the terminal. It is ANSI-aware and Unicode-safe, so colored\n",
+        "output from other tools keeps its alignment.\n",
+        ".SH COMMANDS\n",
+    ));
+    for command in COMMANDS {
+        page.push_str(&format!(".TP\n.B {}\n{}\n", command.name, roff_escape(command.help)));
+    }
+
+    page.push_str(".SH OPTIONS\n.SS General options\n");
+    for option in OPTIONS.iter().filter(|option| option.commands.is_empty()) {
+        push_option(&mut page, option);
+    }
+    for command in COMMANDS {
+        let specific: Vec<&OptionSpec> = OPTIONS
+            .iter()
+            .filter(|option| option.commands.contains(&command.name))
+            .collect();
+        if specific.is_empty() {
+            continue;
+        }
+        page.push_str(&format!(".SS {} options\n", command.name));
+        for option in specific {
+            push_option(&mut page, option);
+        }
+    }
+
+    page.push_str(".SH ENVIRONMENT\n");
+    for (var, key) in ENV_VARS {
+        // The option taking the setting's value, or the flag turning it on
+        let flag = OPTIONS
+            .iter()
+            .find(|option| matches!(option.action, OptionAction::Set(k) | OptionAction::Fixed(k, "true") if k == *key))
+            .map(|option| format!(" (see \\fB\\-\\-{}\\fR)", roff_escape(option.long)))
+            .unwrap_or_default();
+        page.push_str(&format!(".TP\n.B {}\nDefault for the \\fI{}\\fR setting{}.\n", var, roff_escape(key), flag));
+    }
+    page.push_str(&format!(
+        ".TP\n.B {}\nProfile to use when \\fB\\-\\-profile\\fR is not given.\n",
+        PROFILE_ENV_VAR
+    ));
+    page.push_str(".PP\nCommand line options override environment variables, which override config files.\n");
+    let excluded: Vec<String> = NO_ENV_OPTIONS.iter().map(|long| format!("\\fB\\-\\-{}\\fR", roff_escape(long))).collect();
+    page.push_str(&format!(".PP\n{} apply to one run and have no variable.\n", excluded.join(", ")));
+
+    page.push_str(&format!(
+        concat!(
+            ".SH FILES\n",
+            ".TP\n",
+            ".I $XDG_CONFIG_HOME/rolo/config.toml\n",
+            "User defaults (\\fI~/.config/rolo/config.toml\\fR when \\fBXDG_CONFIG_HOME\\fR is unset).\n",
+            "\\fBrolo init\\fR writes a commented starter file here.\n",
+            ".TP\n",
+            ".I {}\n",
+            "Project defaults, found in the working directory or its parents.\n",
+            "Both files hold top-level settings and \\fB[profile.\\fR\\fINAME\\fR\\fB]\\fR tables.\n",
+        ),
+        PROJECT_CONFIG_NAME
+    ));
+
+    page.push_str(".SH EXIT STATUS\n");
+    for (code, meaning) in [
+        (0, "Success."),
+        (EXIT_USAGE, "Invalid options or arguments."),
+        (EXIT_LAYOUT, "The input can't be laid out as requested, e.g. the width is too small."),
+        (EXIT_IO, "Reading input, writing output or running a command failed."),
+        (EXIT_CONFIG, "A config file can't be read or holds an invalid setting."),
+        (BROKEN_PIPE_EXIT_CODE, "The output pipe was closed early; nothing is reported."),
+    ] {
+        page.push_str(&format!(".TP\n.B {}\n{}\n", code, meaning));
+    }
+    page.push_str(".PP\n\\fBexec\\fR exits with the status of its command when layout succeeds.\n");
+
+    page.push_str(".SH EXAMPLES\n");
+    for (description, example) in EXAMPLES {
+        page.push_str(&format!(".PP\n{}:\n.PP\n.RS 4\n.nf\n{}\n.fi\n.RE\n", roff_escape(description), roff_escape(example)));
+    }
+
+    page.push_str(".SH SEE ALSO\n.BR column (1),\n.BR pr (1),\n.BR paste (1)\n");
+    page
+}
+
+/// One `.TP` entry for an option
+fn push_option(page: &mut String, option: &OptionSpec) {
+    let mut names = format!("\\fB\\-\\-{}\\fR", roff_escape(option.long));
+    if let Some(short) = option.short {
+        names.push_str(&format!(", \\fB\\-{}\\fR", short));
+    }
+    match option.value {
+        Some(value) if option.value_optional => names.push_str(&format!(" [\\fI{}\\fR]", value)),
+        Some(value) => names.push_str(&format!(" \\fI{}\\fR", value)),
+        None => {}
+    }
+
+    page.push_str(&format!(".TP\n{}\n{}\n", names, roff_escape(&option.summary())));
+}
+
+/// Escape text for roff: backslashes, hyphens and leading control characters
+fn roff_escape(text: &str) -> String {
+    let escaped = text.replace('\\', "\\e").replace('-', "\\-");
+    if escaped.starts_with('.') || escaped.starts_with('\'') {
+        format!("\\&{}", escaped)
+    } else {
+        escaped
+    }
+}
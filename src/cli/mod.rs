@@ -6,7 +6,14 @@ mod helpers; // Internal implementation details per MODULE_SPEC
 pub mod error;
 pub mod utils;
 pub mod dispatch;
+pub mod options;
+pub mod completions;
+pub mod manpage;
+pub mod explain;
 
 // Re-export public APIs
 pub use utils::*;
-pub use dispatch::*;
\ No newline at end of file
+pub use dispatch::*;
+pub use completions::{completion_script, Shell};
+pub use manpage::man_page;
+pub use explain::{explain_layout, ExplainFormat, LayoutExplanation};
\ No newline at end of file
@@ -0,0 +1,407 @@
+//! Declarative option and subcommand tables
+//!
+//! [`OPTIONS`] and [`COMMANDS`] are the single description of rolo's command
+//! line: `parse_args` looks flags up here, and the help text, man page and
+//! shell completion scripts are generated from the same entries, so none of
+//! them can drift apart.
+
+use crate::cli::helpers::{LIST_STYLES, MAX_COLUMNS, MAX_GAP};
+use crate::width::{MAX_WIDTH, MIN_WIDTH};
+
+/// Alignment names accepted by `--align`
+pub const ALIGNMENTS: &[&str] = &["left", "right", "center"];
+
+/// Line ending names accepted by `--eol`
+pub const LINE_ENDINGS: &[&str] = &["lf", "crlf", "cr", "nul"];
+
+/// Encoding names accepted by `--encoding`
+pub const ENCODINGS: &[&str] = &["utf8", "lossy", "latin1"];
+
+/// Table input formats accepted by `--format`
+pub const INPUT_FORMATS: &[&str] = &["auto", "delimited", "csv", "json", "jsonl"];
+
+/// Array styles accepted by `--arrays`
+pub const ARRAY_STYLES: &[&str] = &["json", "index", "join"];
+
+/// Color policies accepted by `--color`
+pub const COLOR_POLICIES: &[&str] = &["auto", "always", "never"];
+
+/// Formats accepted by `--explain`
+pub const EXPLAIN_FORMATS: &[&str] = &["text", "json"];
+
+/// Formats accepted by `--error-format`
+pub const ERROR_FORMATS: &[&str] = &["text", "json"];
+
+/// Shells supported by `rolo completions`
+pub const SHELLS: &[&str] = &["bash", "zsh", "fish"];
+
+/// Layout mode words, usable as subcommands or after watch/exec
+pub const MODES: &[&str] = &["columns", "table", "list"];
+
+/// General worked examples as `(description, command line)`
+pub const EXAMPLES: &[(&str, &str)] = &[
+    ("Lay out a word list in four columns", "printf '%s\\n' $LIST | rolo --cols 4"),
+    ("Render tab-separated data as a table", "cat data.tsv | rolo table"),
+    ("Redraw a process table every two seconds", "rolo watch table -- ps aux"),
+    ("Enable shell completion for bash", "source <(rolo completions bash)"),
+    ("Install the man page", "rolo man > ~/.local/share/man/man1/rolo.1"),
+];
+
+/// What the parser does when it sees an option
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OptionAction {
+    /// Apply a setting with the option's value (see `apply_setting`)
+    Set(&'static str),
+    /// Apply a setting with a fixed value; the option takes no value
+    Fixed(&'static str, &'static str),
+    /// Select a config file profile
+    Profile,
+    /// Write output to a file
+    Output,
+    /// Follow stdin or an optional file
+    Follow,
+    /// Explain the layout, in an optional format
+    Explain,
+    /// Overwrite existing files
+    Force,
+    Help,
+    Version,
+}
+
+/// One command line option
+#[derive(Debug, Clone, Copy)]
+pub struct OptionSpec {
+    /// Long name without the leading dashes
+    pub long: &'static str,
+    pub short: Option<char>,
+    /// Other accepted long names, not shown in help
+    pub aliases: &'static [&'static str],
+    /// Placeholder for the option's value, if it takes one
+    pub value: Option<&'static str>,
+    /// The value may be omitted (`--follow [FILE]`)
+    pub value_optional: bool,
+    /// Complete set of accepted values, if the value is an enumeration
+    pub values: &'static [&'static str],
+    /// Inclusive range of a numeric value
+    pub range: Option<(usize, usize)>,
+    /// Value used when the option isn't given
+    pub default: Option<&'static str>,
+    pub action: OptionAction,
+    pub help: &'static str,
+    /// Subcommands the option affects; empty for general options
+    pub commands: &'static [&'static str],
+}
+
+impl OptionSpec {
+    /// Option without a value
+    pub const fn flag(long: &'static str, action: OptionAction, help: &'static str) -> Self {
+        OptionSpec { long, short: None, aliases: &[], value: None, value_optional: false, values: &[], range: None, default: None, action, help, commands: &[] }
+    }
+
+    /// Option taking a value shown as `placeholder`
+    pub const fn with_value(long: &'static str, placeholder: &'static str, action: OptionAction, help: &'static str) -> Self {
+        OptionSpec { value: Some(placeholder), ..Self::flag(long, action, help) }
+    }
+
+    pub const fn short(self, short: char) -> Self {
+        OptionSpec { short: Some(short), ..self }
+    }
+
+    pub const fn aliases(self, aliases: &'static [&'static str]) -> Self {
+        OptionSpec { aliases, ..self }
+    }
+
+    pub const fn values(self, values: &'static [&'static str]) -> Self {
+        OptionSpec { values, ..self }
+    }
+
+    pub const fn optional(self) -> Self {
+        OptionSpec { value_optional: true, ..self }
+    }
+
+    pub const fn range(self, min: usize, max: usize) -> Self {
+        OptionSpec { range: Some((min, max)), ..self }
+    }
+
+    pub const fn default(self, default: &'static str) -> Self {
+        OptionSpec { default: Some(default), ..self }
+    }
+
+    pub const fn commands(self, commands: &'static [&'static str]) -> Self {
+        OptionSpec { commands, ..self }
+    }
+
+    /// Whether the option affects `command` (general options affect every command)
+    pub fn applies_to(&self, command: &str) -> bool {
+        self.commands.is_empty() || self.commands.contains(&command)
+    }
+
+    /// Whether `flag` (e.g. `--delim`, `-n`) names this option
+    pub fn matches(&self, flag: &str) -> bool {
+        match flag.strip_prefix("--") {
+            Some(long) => long == self.long || self.aliases.contains(&long),
+            None => {
+                let mut chars = flag.chars();
+                chars.next() == Some('-') && chars.next() == self.short && chars.next().is_none()
+            }
+        }
+    }
+
+    /// Whether the value is a file path
+    pub fn takes_file(&self) -> bool {
+        self.value == Some("FILE")
+    }
+
+    /// Option as shown in help, e.g. `--cols=N` or `--output, -o FILE`
+    pub fn label(&self) -> String {
+        let mut label = format!("--{}", self.long);
+        if let Some(short) = self.short {
+            label.push_str(&format!(", -{}", short));
+        }
+        match self.value {
+            Some(value) if self.value_optional => label.push_str(&format!(" [{}]", value)),
+            Some(value) if self.short.is_some() => label.push_str(&format!(" {}", value)),
+            Some(value) => label.push_str(&format!("={}", value)),
+            None => {}
+        }
+        label
+    }
+
+    /// Help text with accepted values and default, e.g. `Alignment (left, right, center; default left)`
+    pub fn summary(&self) -> String {
+        let mut details = Vec::new();
+        if let Some((min, max)) = self.range {
+            details.push(format!("{}-{}", min, max));
+        }
+        if !self.values.is_empty() {
+            details.push(self.values.join(", "));
+        }
+        match (self.default, self.value) {
+            (Some(_), None) => details.push("default".to_string()),
+            (Some(default), Some(_)) => details.push(format!("default {}", default)),
+            (None, _) => {}
+        }
+
+        if details.is_empty() {
+            self.help.to_string()
+        } else {
+            format!("{} ({})", self.help, details.join("; "))
+        }
+    }
+
+    /// Accepted values for error messages, e.g. ` (left, right, center)`
+    pub fn value_hint(&self) -> String {
+        if self.values.is_empty() {
+            String::new()
+        } else {
+            format!(" ({})", self.values.join(", "))
+        }
+    }
+}
+
+/// All options, in help order
+pub const OPTIONS: &[OptionSpec] = &[
+    OptionSpec::with_value("cols", "N", OptionAction::Set("cols"), "Number of columns")
+        .range(1, MAX_COLUMNS)
+        .default("2")
+        .commands(&["columns"]),
+    OptionSpec::with_value("width", "N", OptionAction::Set("width"), "Terminal width")
+        .range(MIN_WIDTH, MAX_WIDTH)
+        .default("terminal width, or 80 with --no-fit")
+        .commands(MODES),
+    OptionSpec::with_value("gap", "N", OptionAction::Set("gap"), "Gap between columns")
+        .range(0, MAX_GAP)
+        .default("2")
+        .commands(&["columns"]),
+    OptionSpec::with_value("delim", "STR", OptionAction::Set("delimiter"), "Delimiter for input parsing")
+        .aliases(&["delimiter", "sep"])
+        .default("tab for tables, comma for CSV")
+        .commands(MODES),
+    OptionSpec::with_value("format", "FORMAT", OptionAction::Set("format"), "Table input format; auto means csv with --delim ','")
+        .values(INPUT_FORMATS)
+        .default("auto")
+        .commands(&["table"]),
+    OptionSpec::with_value("max-depth", "N", OptionAction::Set("max_depth"), "Levels of nested JSON to flatten into dot-path columns")
+        .default("all")
+        .commands(&["table"]),
+    OptionSpec::with_value("arrays", "STYLE", OptionAction::Set("arrays"), "JSON arrays as compact JSON, tags[0] columns or joined cells")
+        .values(ARRAY_STYLES)
+        .default("json")
+        .commands(&["table"]),
+    OptionSpec::with_value("fields", "LIST", OptionAction::Set("fields"), "Keep these columns, in order: names or paths like user.city")
+        .commands(&["table"]),
+    OptionSpec::flag("table", OptionAction::Fixed("mode", "table"), "Same as the table command"),
+    OptionSpec::flag("list", OptionAction::Fixed("mode", "list"), "Same as the list command"),
+    OptionSpec::flag("line-numbers", OptionAction::Fixed("line_numbers", "true"), "Add line numbers to list mode")
+        .short('n')
+        .commands(&["list"]),
+    OptionSpec::with_value("list-style", "STYLE", OptionAction::Set("list_style"), "List style")
+        .values(LIST_STYLES)
+        .commands(&["list"]),
+    OptionSpec::with_value("align", "ALIGN", OptionAction::Set("alignment"), "Alignment")
+        .values(ALIGNMENTS)
+        .default("left")
+        .commands(&["list"]),
+    OptionSpec::flag("fit", OptionAction::Fixed("fit", "true"), "Fit to terminal width")
+        .default("on")
+        .commands(MODES),
+    OptionSpec::flag("no-fit", OptionAction::Fixed("fit", "false"), "Use fixed width").commands(MODES),
+    OptionSpec::with_value("follow", "FILE", OptionAction::Follow, "Keep reading input and redraw the layout as it grows")
+        .short('f')
+        .optional()
+        .commands(MODES),
+    OptionSpec::with_value("interval", "TIME", OptionAction::Set("interval"), "Watch interval, e.g. 2s, 500ms, 1m")
+        .default("2s")
+        .commands(&["watch"]),
+    OptionSpec::with_value("pre", "CMD", OptionAction::Set("pre"), "Pipe input through CMD before layout (repeatable)"),
+    OptionSpec::with_value("post", "CMD", OptionAction::Set("post"), "Pipe output through CMD after layout (repeatable)"),
+    OptionSpec::with_value("eol", "STYLE", OptionAction::Set("eol"), "Output line ending; input is auto-detected")
+        .values(LINE_ENDINGS)
+        .default("lf"),
+    OptionSpec::flag("crlf", OptionAction::Fixed("eol", "crlf"), "Same as --eol=crlf"),
+    OptionSpec::with_value("encoding", "ENC", OptionAction::Set("encoding"), "Input encoding")
+        .values(ENCODINGS)
+        .default("utf8"),
+    OptionSpec::with_value("output", "FILE", OptionAction::Output, "Write output to FILE atomically instead of stdout").short('o'),
+    OptionSpec::with_value("color", "WHEN", OptionAction::Set("color"), "Keep ANSI colors")
+        .values(COLOR_POLICIES)
+        .default("always"),
+    OptionSpec::flag("binary-ok", OptionAction::Fixed("binary_ok", "true"), "Accept binary-looking input, showing control bytes as ^X"),
+    OptionSpec::flag("null-data", OptionAction::Fixed("null_data", "true"), "Input records are NUL-delimited (find -print0)").short('z'),
+    OptionSpec::flag("null-output", OptionAction::Fixed("eol", "nul"), "Terminate output lines with NUL (same as --eol=nul)"),
+    OptionSpec::with_value("explain", "FORMAT", OptionAction::Explain, "Explain widths, scaling and truncation on stderr")
+        .optional()
+        .values(EXPLAIN_FORMATS)
+        .default("text")
+        .commands(MODES),
+    OptionSpec::with_value("error-format", "FORMAT", OptionAction::Set("error_format"), "Report errors as text or as JSON {kind, message, hint}")
+        .values(ERROR_FORMATS)
+        .default("text"),
+    OptionSpec::flag("verbose", OptionAction::Fixed("verbose", "true"), "Also show build features, width strategy and platform")
+        .short('v')
+        .commands(&["version"]),
+    OptionSpec::flag("force", OptionAction::Force, "Overwrite an existing config file").commands(&["init"]),
+    OptionSpec::with_value("profile", "NAME", OptionAction::Profile, "Use the [profile.NAME] settings from the config files"),
+    OptionSpec::flag("help", OptionAction::Help, "Show help, or a command's help after the command").short('h'),
+    OptionSpec::flag("version", OptionAction::Version, "Show version information").short('V'),
+];
+
+/// One subcommand
+#[derive(Debug, Clone, Copy)]
+pub struct CommandSpec {
+    pub name: &'static str,
+    pub help: &'static str,
+    pub usage: &'static str,
+    /// Positional arguments the command accepts after its name
+    pub arguments: &'static [&'static str],
+    /// Worked examples as `(description, command line)`, run from the repository root
+    pub examples: &'static [(&'static str, &'static str)],
+}
+
+/// Names of all subcommands, in [`COMMANDS`] order
+pub const COMMAND_NAMES: &[&str] = &[
+    "list", "table", "columns", "watch", "exec", "config", "completions", "man", "init", "help", "version",
+];
+
+/// All subcommands, in help order
+pub const COMMANDS: &[CommandSpec] = &[
+    CommandSpec {
+        name: "list",
+        help: "Format as list with optional line numbers",
+        usage: "rolo list [OPTIONS] < FILE",
+        arguments: &[],
+        examples: &[
+            ("Numbered list", "rolo list -n < tests/data/simple_list.txt"),
+            ("Bulleted list, right-aligned in 40 columns", "rolo list --list-style bullets --align right --width 40 < tests/data/simple_list.txt"),
+            ("Every comma-separated field as an item", "rolo list --delim , < tests/data/sample.csv"),
+        ],
+    },
+    CommandSpec {
+        name: "table",
+        help: "Format as table with delimiter detection",
+        usage: "rolo table [OPTIONS] < FILE",
+        arguments: &[],
+        examples: &[
+            ("Tab-separated data", "rolo table < tests/data/sample.tsv"),
+            ("Comma-separated data", "rolo table --delim , < tests/data/sample.csv"),
+            ("Long cells truncated to 60 columns", "rolo table --width 60 < tests/data/long_content.tsv"),
+            ("Why columns were scaled and cells truncated", "rolo table --width 60 --explain < tests/data/long_content.tsv"),
+        ],
+    },
+    CommandSpec {
+        name: "columns",
+        help: "Format as columns (default)",
+        usage: "rolo [columns] [OPTIONS] < FILE",
+        arguments: &[],
+        examples: &[
+            ("Three columns", "rolo --cols 3 < tests/data/simple_list.txt"),
+            ("Two wide-spaced columns in 40 characters", "rolo columns --gap 6 --width 40 < tests/data/simple_list.txt"),
+        ],
+    },
+    CommandSpec {
+        name: "watch",
+        help: "Re-run a command and redraw its layout: watch [MODE] -- CMD",
+        usage: "rolo watch [MODE] [OPTIONS] -- CMD [ARGS...]",
+        arguments: &[],
+        examples: &[("Redraw a table every five seconds", "rolo watch table --interval 5s -- cat tests/data/sample.tsv")],
+    },
+    CommandSpec {
+        name: "exec",
+        help: "Run a command and lay out its output: exec [MODE] -- CMD",
+        usage: "rolo exec [MODE] [OPTIONS] -- CMD [ARGS...]",
+        arguments: &[],
+        examples: &[("Lay out a command's output as a table", "rolo exec table -- cat tests/data/sample.tsv")],
+    },
+    CommandSpec {
+        name: "config",
+        help: "Show effective settings and where they came from: config show",
+        usage: "rolo config [show] [--profile NAME]",
+        arguments: &["show"],
+        examples: &[("Settings of the 'wide' profile", "rolo config show --profile wide")],
+    },
+    CommandSpec {
+        name: "completions",
+        help: "Print a shell completion script: completions bash|zsh|fish",
+        usage: "rolo completions bash|zsh|fish",
+        arguments: SHELLS,
+        examples: &[("Enable completion in the current bash session", "source <(rolo completions bash)")],
+    },
+    CommandSpec {
+        name: "man",
+        help: "Print the rolo(1) man page in roff format",
+        usage: "rolo man",
+        arguments: &[],
+        examples: &[("Install the man page", "rolo man > ~/.local/share/man/man1/rolo.1")],
+    },
+    CommandSpec {
+        name: "init",
+        help: "Write a starter config file and show the detected environment",
+        usage: "rolo init [--force]",
+        arguments: &[],
+        examples: &[("Replace the user config with a fresh starter config", "rolo init --force")],
+    },
+    CommandSpec {
+        name: "help",
+        help: "Show help for rolo or one command",
+        usage: "rolo help [COMMAND]",
+        arguments: COMMAND_NAMES,
+        examples: &[("Options of the table command", "rolo help table")],
+    },
+    CommandSpec {
+        name: "version",
+        help: "Show version information",
+        usage: "rolo version [--verbose]",
+        arguments: &[],
+        examples: &[("Version details for a bug report", "rolo version --verbose")],
+    },
+];
+
+/// Option named by `flag` (`--long`, an alias, or `-s`)
+pub fn find_option(flag: &str) -> Option<&'static OptionSpec> {
+    OPTIONS.iter().find(|spec| spec.matches(flag))
+}
+
+/// Subcommand named `name`
+pub fn find_command(name: &str) -> Option<&'static CommandSpec> {
+    COMMANDS.iter().find(|spec| spec.name == name)
+}
@@ -1,10 +1,22 @@
 //! CLI utilities - public API per MODULE_SPEC
 
-use crate::cli::error::CliError;
-use crate::cli::helpers::{parse_column_count, show_version, show_help};
+use crate::cli::error::{CliError, ErrorFormat};
+use crate::cli::explain::{explain_layout, ExplainFormat};
+use crate::cli::helpers::{delimiter_warning, parse_alignment, parse_column_count, parse_fields, parse_gap, parse_list_style, unfit_columns};
+use crate::cli::options::{find_command, find_option, OptionAction};
+use crate::config::ValueSource;
+use crate::format::{parse_table_with_config, select_fields, ArrayStyle, FlattenConfig, InputFormat};
 use crate::width::{validate_width, get_terminal_width};
-use crate::layout::{format_columns_with_delimiter, format_list_with_config, LayoutConfig, ListConfig, ListAlignment};
-use crate::stream::read_stdin;
+use crate::layout::error::LayoutError;
+use crate::layout::{split_column_items, format_columns_with_delimiter, format_list_items, format_list_with_config, format_table_model, LayoutConfig, ListConfig, ListAlignment, TableModel};
+use crate::stream::{
+    check_binary, decode_input, exec_command_bytes, filter_through_commands, follow_input, parse_interval,
+    prepare_text, read_stdin_decoded, shell_join, split_records, watch_command, write_file_atomic, ColorPolicy,
+    FollowConfig, FollowSource, InputEncoding, LineEnding, OutputWriter, StreamConfig, WatchConfig,
+};
+use std::borrow::Cow;
+use std::path::PathBuf;
+use std::time::Duration;
 
 /// CLI configuration structure
 #[derive(Debug, Clone)]
@@ -14,6 +26,12 @@ pub struct CliConfig {
     pub width: Option<usize>,
     pub gap: Option<usize>,
     pub delimiter: Option<String>,
+    /// How table input is parsed (`--format`)
+    pub input_format: InputFormat,
+    /// How nested JSON becomes columns (`--max-depth`, `--arrays`)
+    pub flatten: FlattenConfig,
+    /// Table columns to keep, in order (`--fields`)
+    pub fields: Vec<String>,
     pub fit_mode: bool,
     pub line_numbers: bool,
     pub list_style: Option<String>,
@@ -21,6 +39,43 @@ pub struct CliConfig {
     pub headers: bool,
     pub help: bool,
     pub version: bool,
+    /// Subcommand word given on the command line (list, table, watch, ...)
+    pub subcommand: Option<String>,
+    /// Keep reading input and redraw as it grows
+    pub follow: bool,
+    /// File to follow instead of stdin
+    pub follow_file: Option<PathBuf>,
+    /// Delay between watch runs
+    pub interval: Duration,
+    /// Filter commands applied before layout, in order
+    pub pre_commands: Vec<String>,
+    /// Filter commands applied after layout, in order
+    pub post_commands: Vec<String>,
+    pub line_ending: LineEnding,
+    /// Input records are NUL-delimited
+    pub null_data: bool,
+    pub encoding: InputEncoding,
+    /// Lay out binary-looking input instead of refusing it
+    pub binary_ok: bool,
+    /// Write output atomically to this file instead of stdout
+    pub output: Option<PathBuf>,
+    pub color: ColorPolicy,
+    /// Command words after `--`, run by watch and exec
+    pub command: Vec<String>,
+    /// Positional arguments of `config` and `help` (e.g. `show`)
+    pub arguments: Vec<String>,
+    /// Config file profile selected with `--profile`
+    pub profile: Option<String>,
+    /// Where `width` came from: a flag, a `ROLO_*` variable or a config file
+    pub width_source: ValueSource,
+    /// Report how the layout was sized on stderr (`--explain`)
+    pub explain: Option<ExplainFormat>,
+    /// How failures are reported on stderr
+    pub error_format: ErrorFormat,
+    /// Let `init` overwrite an existing config file
+    pub force: bool,
+    /// Detailed `version` output
+    pub verbose: bool,
 }
 
 impl Default for CliConfig {
@@ -31,6 +86,9 @@ impl Default for CliConfig {
             width: None,
             gap: None,
             delimiter: None,
+            input_format: InputFormat::Auto,
+            flatten: FlattenConfig::default(),
+            fields: Vec::new(),
             fit_mode: true, // Default to fit mode
             line_numbers: false,
             list_style: None,
@@ -38,206 +96,556 @@ impl Default for CliConfig {
             headers: false,
             help: false,
             version: false,
+            subcommand: None,
+            follow: false,
+            follow_file: None,
+            interval: Duration::from_secs(2),
+            pre_commands: Vec::new(),
+            post_commands: Vec::new(),
+            line_ending: LineEnding::Unix,
+            null_data: false,
+            encoding: InputEncoding::Utf8,
+            binary_ok: false,
+            output: None,
+            color: ColorPolicy::Always,
+            command: Vec::new(),
+            arguments: Vec::new(),
+            profile: None,
+            width_source: ValueSource::Default,
+            explain: None,
+            error_format: ErrorFormat::Text,
+            force: false,
+            verbose: false,
+        }
+    }
+}
+
+impl CliConfig {
+    /// Layout width: explicit `--width`, else the terminal in fit mode, else 80
+    pub fn layout_width(&self) -> usize {
+        if self.fit_mode {
+            self.width.unwrap_or_else(get_terminal_width)
+        } else {
+            self.width.unwrap_or(80) // Fixed width when not in fit mode
+        }
+    }
+
+    /// Separator of columns and list items: NUL with `--null-data`, else the delimiter
+    pub fn item_delimiter(&self) -> Option<&str> {
+        if self.null_data { Some("\0") } else { self.delimiter.as_deref() }
+    }
+
+    /// Cell delimiter of table mode (tab, or comma for CSV, unless `--delim` is given)
+    pub fn table_delimiter(&self) -> &str {
+        match (&self.delimiter, self.input_format) {
+            (Some(delimiter), _) => delimiter,
+            (None, InputFormat::Csv) => ",",
+            (None, _) => "\t",
+        }
+    }
+
+    /// Stream settings for reading input and writing output
+    pub fn stream_config(&self) -> StreamConfig {
+        StreamConfig {
+            line_ending: self.line_ending,
+            null_data: self.null_data,
+            encoding: self.encoding,
+            allow_binary: self.binary_ok,
+            color: self.color,
+            ..StreamConfig::default()
         }
     }
 }
 
 /// CLI operating modes
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum CliMode {
     Columns,
     Table,
     List,
 }
 
+impl CliMode {
+    /// Mode for a subcommand word, if it names a layout mode
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "columns" => Some(CliMode::Columns),
+            "table" => Some(CliMode::Table),
+            "list" => Some(CliMode::List),
+            _ => None,
+        }
+    }
+
+    /// Subcommand word for this mode
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CliMode::Columns => "columns",
+            CliMode::Table => "table",
+            CliMode::List => "list",
+        }
+    }
+}
 
 /// Parse command line arguments into configuration
+///
+/// `args[0]` is the program name. Options take their value either as the
+/// next argument or inline (`--cols=3`); everything after `--` is the command
+/// run by watch and exec. Values are validated here, and unknown options or
+/// words are rejected.
 pub fn parse_args(args: &[String]) -> Result<CliConfig, CliError> {
-    let mut config = CliConfig::default();
+    parse_args_onto(CliConfig::default(), args)
+}
+
+/// Parse command line arguments on top of an existing configuration
+///
+/// Used to layer CLI flags over config file defaults: anything the
+/// arguments set wins, everything else keeps its value from `base`.
+pub fn parse_args_onto(base: CliConfig, args: &[String]) -> Result<CliConfig, CliError> {
+    let mut config = base;
+    let mut set_keys = Vec::new();
+    parse_into(&mut config, args, &mut set_keys)?;
+    if set_keys.contains(&"width") {
+        config.width_source = ValueSource::Cli;
+    }
+    Ok(config)
+}
+
+/// Apply a named setting, validating its value
+///
+/// Settings are the shared vocabulary of CLI flags and config files, so a
+/// value is checked the same way wherever it comes from. Keys: `mode`,
+/// `cols`, `width`, `gap`, `delimiter`, `format`, `max_depth`, `arrays`, `fields`, `fit`, `line_numbers`, `list_style`,
+/// `alignment`, `interval`, `eol`, `encoding`, `null_data`, `binary_ok`,
+/// `color`, `error_format`, `verbose`, `pre`, `post` (the last two append).
+pub fn apply_setting(config: &mut CliConfig, key: &str, value: &str) -> Result<(), CliError> {
+    match key {
+        "mode" => {
+            config.mode = CliMode::from_name(value).ok_or_else(|| {
+                CliError::InvalidArgument(format!("Invalid mode: {}. Use columns, table, or list", value))
+            })?;
+        }
+        "cols" => config.columns = Some(parse_column_count(value)?),
+        "width" => {
+            let width = validate_width(value).map_err(|e| CliError::InvalidWidth(format!("{}", e)))?;
+            config.width = Some(width);
+        }
+        "gap" => config.gap = Some(parse_gap(value)?),
+        "delimiter" => config.delimiter = Some(value.to_string()),
+        "format" => {
+            config.input_format = InputFormat::parse(value).ok_or_else(|| {
+                CliError::InvalidArgument(format!("Invalid input format: {} (use auto, delimited, csv, json or jsonl)", value))
+            })?;
+        }
+        "max_depth" => {
+            let depth = value.parse().map_err(|_| CliError::InvalidArgument(format!("Invalid max depth: {}", value)))?;
+            config.flatten.max_depth = Some(depth);
+        }
+        "arrays" => {
+            config.flatten.arrays = ArrayStyle::parse(value).ok_or_else(|| {
+                CliError::InvalidArgument(format!("Invalid array style: {} (use json, index or join)", value))
+            })?;
+        }
+        "fields" => config.fields = parse_fields(value)?,
+        "fit" => config.fit_mode = parse_flag(key, value)?,
+        "line_numbers" => config.line_numbers = parse_flag(key, value)?,
+        "list_style" => config.list_style = Some(parse_list_style(value)?),
+        "alignment" => config.alignment = parse_alignment(value)?,
+        "interval" => {
+            config.interval = parse_interval(value).ok_or_else(|| {
+                CliError::InvalidArgument(format!("Invalid interval: {} (use e.g. 2s, 500ms, 1m)", value))
+            })?;
+        }
+        "eol" => {
+            config.line_ending = LineEnding::parse(value).ok_or_else(|| {
+                CliError::InvalidArgument(format!("Invalid line ending: {} (use lf, crlf, cr or nul)", value))
+            })?;
+        }
+        "encoding" => {
+            config.encoding = InputEncoding::parse(value).ok_or_else(|| {
+                CliError::InvalidArgument(format!("Invalid encoding: {} (use utf8, lossy or latin1)", value))
+            })?;
+        }
+        "null_data" => config.null_data = parse_flag(key, value)?,
+        "verbose" => config.verbose = parse_flag(key, value)?,
+        "binary_ok" => config.binary_ok = parse_flag(key, value)?,
+        "color" => {
+            config.color = ColorPolicy::parse(value).ok_or_else(|| {
+                CliError::InvalidArgument(format!("Invalid color policy: {} (use auto, always or never)", value))
+            })?;
+        }
+        "error_format" => {
+            config.error_format = ErrorFormat::parse(value).ok_or_else(|| {
+                CliError::InvalidArgument(format!("Invalid error format: {} (use text or json)", value))
+            })?;
+        }
+        "pre" => config.pre_commands.push(value.to_string()),
+        "post" => config.post_commands.push(value.to_string()),
+        _ => return Err(CliError::InvalidArgument(format!("Unknown setting: {}", key))),
+    }
+    Ok(())
+}
+
+/// Parse arguments into `config`, recording the settings they touched
+pub(crate) fn parse_into(config: &mut CliConfig, args: &[String], set_keys: &mut Vec<&'static str>) -> Result<(), CliError> {
     let mut i = 1; // Skip program name
 
     while i < args.len() {
-        match args[i].as_str() {
-            "--help" | "-h" => {
-                config.help = true;
-                break; // Help takes precedence
-            }
-            "--version" | "-V" => {
-                config.version = true;
-                break; // Version takes precedence
+        let arg = args[i].as_str();
+
+        // Everything after `--` belongs to the wrapped command
+        if arg == "--" {
+            config.command = args[i + 1..].to_vec();
+            break;
+        }
+
+        let (flag, inline_value) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => (flag, Some(value.to_string())),
+            _ => (arg, None),
+        };
+
+        // Value for the current option, inline or from the next argument
+        let mut value = |what: &str| -> Result<String, CliError> {
+            if let Some(value) = inline_value.clone() {
+                return Ok(value);
             }
-            "--cols" => {
-                i += 1;
-                if i >= args.len() {
-                    return Err(CliError::MissingArgument("--cols requires a value".to_string()));
+            i += 1;
+            args.get(i)
+                .cloned()
+                .ok_or_else(|| CliError::MissingArgument(format!("{} requires a value{}", flag, what)))
+        };
+
+        let setting: Option<(&'static str, String)> = match find_option(flag) {
+            Some(spec) => match spec.action {
+                OptionAction::Help => {
+                    config.help = true;
+                    break; // Help takes precedence
                 }
-                config.columns = Some(parse_column_count(&args[i])?);
-            }
-            "--width" => {
-                i += 1;
-                if i >= args.len() {
-                    return Err(CliError::MissingArgument("--width requires a value".to_string()));
+                OptionAction::Version => {
+                    config.version = true;
+                    config.verbose |= args[i + 1..].iter().take_while(|arg| *arg != "--").any(|arg| arg == "--verbose" || arg == "-v");
+                    break; // Version takes precedence
                 }
-                let width = validate_width(&args[i])
-                    .map_err(|e| CliError::InvalidWidth(format!("{}", e)))?;
-                config.width = Some(width);
-            }
-            "--gap" => {
-                i += 1;
-                if i >= args.len() {
-                    return Err(CliError::MissingArgument("--gap requires a value".to_string()));
+                OptionAction::Set(key) => Some((key, value(&spec.value_hint())?)),
+                OptionAction::Fixed(key, fixed) => Some((key, fixed.to_string())),
+                OptionAction::Profile => {
+                    config.profile = Some(value("")?);
+                    None
                 }
-                match args[i].parse::<usize>() {
-                    Ok(gap) if gap <= 20 => config.gap = Some(gap),
-                    Ok(gap) => return Err(CliError::InvalidArgument(format!("Gap too large (max 20): {}", gap))),
-                    Err(_) => return Err(CliError::InvalidArgument(format!("Invalid gap value: {}", args[i]))),
+                OptionAction::Output => {
+                    config.output = Some(PathBuf::from(value("")?));
+                    None
                 }
-            }
-            "--delim" | "--delimiter" | "--sep" => {
-                i += 1;
-                if i >= args.len() {
-                    return Err(CliError::MissingArgument("--delim requires a value".to_string()));
+                OptionAction::Explain => {
+                    // The format is optional, so only a format name is taken from the next argument
+                    let format = match inline_value {
+                        Some(format) => format,
+                        None => match args.get(i + 1).filter(|next| ExplainFormat::parse(next).is_some()) {
+                            Some(next) => {
+                                i += 1;
+                                next.clone()
+                            }
+                            None => "text".to_string(),
+                        },
+                    };
+                    config.explain = Some(ExplainFormat::parse(&format).ok_or_else(|| {
+                        CliError::InvalidArgument(format!("Invalid explain format: {}{}", format, spec.value_hint()))
+                    })?);
+                    None
                 }
-                config.delimiter = Some(args[i].clone());
-            }
-            "--table" => {
-                config.mode = CliMode::Table;
-            }
-            "--fit" => {
-                config.fit_mode = true;
-            }
-            "--no-fit" => {
-                config.fit_mode = false;
-            }
-            "--list" => {
-                config.mode = CliMode::List;
-            }
-            "--line-numbers" | "-n" => {
-                config.line_numbers = true;
-            }
-            "--list-style" => {
-                i += 1;
-                if i >= args.len() {
-                    return Err(CliError::MissingArgument("--list-style requires a value (numbers, bullets, stars, dots, dash)".to_string()));
+                OptionAction::Force => {
+                    config.force = true;
+                    None
                 }
-                config.list_style = Some(args[i].clone());
-            }
-            "--align" => {
-                i += 1;
-                if i >= args.len() {
-                    return Err(CliError::MissingArgument("--align requires a value (left, right, center)".to_string()));
+                OptionAction::Follow => {
+                    config.follow = true;
+                    if let Some(file) = inline_value {
+                        config.follow_file = Some(PathBuf::from(file));
+                    } else if let Some(next) = args.get(i + 1).filter(|next| is_follow_file(next)) {
+                        config.follow_file = Some(PathBuf::from(next));
+                        i += 1;
+                    }
+                    None
                 }
-                config.alignment = match args[i].to_lowercase().as_str() {
-                    "left" | "l" => ListAlignment::Left,
-                    "right" | "r" => ListAlignment::Right,
-                    "center" | "c" => ListAlignment::Center,
-                    _ => return Err(CliError::InvalidArgument(format!("Invalid alignment: {}. Use left, right, or center", args[i]))),
-                };
-            }
-            "table" => config.mode = CliMode::Table,
-            "list" => config.mode = CliMode::List,
-            "columns" => config.mode = CliMode::Columns,
-            arg if arg.starts_with("--") => {
+            },
+            None if arg.starts_with('-') => {
                 return Err(CliError::InvalidArgument(format!("Unknown option: {}", arg)));
             }
-            _ => {
-                // Positional arguments (subcommands without --)
-                match args[i].as_str() {
-                    "table" => config.mode = CliMode::Table,
-                    "list" => config.mode = CliMode::List,
-                    "columns" => config.mode = CliMode::Columns,
-                    _ => return Err(CliError::UnsupportedCommand(args[i].clone())),
+            // Positional arguments: the subcommand, then a layout mode or its arguments
+            None => {
+                let word = arg;
+                let subcommand = config.subcommand.as_deref().and_then(find_command);
+                match subcommand {
+                    None if find_command(word).is_some() => {
+                        config.subcommand = Some(word.to_string());
+                        CliMode::from_name(word).map(|_| ("mode", word.to_string()))
+                    }
+                    Some(command) if !command.arguments.is_empty() => {
+                        config.arguments.push(word.to_string());
+                        None
+                    }
+                    Some(_) if CliMode::from_name(word).is_some() => Some(("mode", word.to_string())),
+                    _ => return Err(CliError::UnsupportedCommand(word.to_string())),
                 }
             }
+        };
+
+        if let Some((key, value)) = setting {
+            apply_setting(config, key, &value)?;
+            set_keys.push(key);
         }
         i += 1;
     }
 
-    Ok(config)
+    Ok(())
+}
+
+/// Error format asked for by `args` or `ROLO_ERROR_FORMAT`
+///
+/// Used to report errors that stop the arguments from being parsed at all,
+/// so it only looks for `--error-format` and ignores everything else.
+pub fn requested_error_format(args: &[String]) -> ErrorFormat {
+    let mut format = std::env::var("ROLO_ERROR_FORMAT").ok().and_then(|value| ErrorFormat::parse(&value));
+
+    let mut words = args.iter().skip(1).take_while(|arg| *arg != "--");
+    while let Some(arg) = words.next() {
+        let value = match arg.strip_prefix("--error-format") {
+            Some("") => words.next().map(String::as_str),
+            Some(inline) => inline.strip_prefix('='),
+            None => None,
+        };
+        if let Some(parsed) = value.and_then(ErrorFormat::parse) {
+            format = Some(parsed);
+        }
+    }
+
+    format.unwrap_or(ErrorFormat::Text)
+}
+
+/// Parse a boolean setting value
+fn parse_flag(key: &str, value: &str) -> Result<bool, CliError> {
+    match value.to_lowercase().as_str() {
+        "true" | "yes" | "on" | "1" => Ok(true),
+        "false" | "no" | "off" | "0" => Ok(false),
+        _ => Err(CliError::InvalidArgument(format!("Invalid value for {}: {} (use true or false)", key, value))),
+    }
+}
+
+/// Whether the word after `--follow` names a file rather than the next option
+fn is_follow_file(word: &str) -> bool {
+    !word.starts_with('-') && find_command(word).is_none()
 }
 
 /// Execute CLI action based on configuration
+///
+/// Reads stdin (or follows input), lays it out in the configured mode and
+/// writes the result to stdout or the `--output` file.
 pub fn execute_cli(config: &CliConfig) -> Result<(), CliError> {
-    if config.help {
-        show_help();
-        return Ok(());
+    let stream = config.stream_config();
+
+    if config.follow {
+        reject_streaming_options(config, "--follow")?;
+        let follow = FollowConfig {
+            source: match &config.follow_file {
+                Some(file) => FollowSource::File(file.clone()),
+                None => FollowSource::Stdin,
+            },
+            stream,
+            ..FollowConfig::default()
+        };
+        return follow_input(&follow, |input| render_layout(config, input));
     }
 
-    if config.version {
-        show_version();
-        return Ok(());
+    // Read input from stdin (CRLF/CR line endings are normalized on read)
+    let input = read_stdin_decoded(&stream)?;
+    if input.replaced_bytes > 0 {
+        eprintln!("⚠️  Replaced {} invalid UTF-8 byte(s) in input", input.replaced_bytes);
+    }
+    warn_about_input(config, &input.text);
+    let output = render_layout(config, &input.text)?;
+
+    emit_output(config, &output)
+}
+
+/// Re-run the configured command forever, redrawing its layout
+pub fn execute_watch(config: &CliConfig) -> Result<(), CliError> {
+    if config.command.is_empty() {
+        return Err(CliError::MissingArgument(
+            "watch requires a command after --, e.g. rolo watch table -- ps aux".to_string(),
+        ));
+    }
+    reject_streaming_options(config, "watch")?;
+
+    let mut watch = WatchConfig::new(&shell_join(&config.command));
+    watch.interval = config.interval;
+    watch.line_ending = config.line_ending;
+    if config.mode == CliMode::Table {
+        watch.cell_separator = Some(" | ".to_string());
+    }
+
+    // Each run's stdout is normalized like stdin, so CRLF output can't leave `\r` in cells
+    let stream = config.stream_config();
+    watch_command(&watch, |input| render_layout(config, &prepare_text(input, &stream)))
+}
+
+/// Run the configured command once, lay out its stdout and return its exit code
+pub fn execute_exec(config: &CliConfig) -> Result<i32, CliError> {
+    if config.command.is_empty() {
+        return Err(CliError::MissingArgument(
+            "exec requires a command after --, e.g. rolo exec table -- ps aux".to_string(),
+        ));
+    }
+
+    // The command's stdout is checked and decoded like stdin
+    let (stdout, exit_code) = exec_command_bytes(&config.command)?;
+    let stream = config.stream_config();
+    check_binary(&stdout, &stream)?;
+    let decoded = decode_input(stdout, stream.encoding)?;
+    if decoded.replaced_bytes > 0 {
+        eprintln!("⚠️  Replaced {} invalid UTF-8 byte(s) in command output", decoded.replaced_bytes);
+    }
+    let stdout = prepare_text(&decoded.text, &stream);
+    warn_about_input(config, &stdout);
+    let rendered = render_layout(config, &stdout)?;
+
+    if !rendered.is_empty() {
+        emit_output(config, &rendered)?;
+    }
+    Ok(exit_code)
+}
+
+/// Render input through the pre filters, the layout and the post filters
+pub fn render_layout(config: &CliConfig, input: &str) -> Result<String, CliError> {
+    let input = filter_through_commands(&config.pre_commands, input.to_string())?;
+
+    // Explain before drawing, so layouts that fail are explained too
+    if let Some(format) = config.explain {
+        eprintln!("{}", explain_layout(config, &input).render(format));
+    }
+    let rendered = format_input(config, &input)?;
+
+    if config.post_commands.is_empty() {
+        return Ok(rendered);
     }
 
-    // Process input through layout system
+    // Post filters see complete lines; drop the final newline again since the writer adds one
+    let output = filter_through_commands(&config.post_commands, format!("{}\n", rendered))?;
+    Ok(output.strip_suffix('\n').unwrap_or(&output).to_string())
+}
+
+/// Lay out input in the configured mode
+pub fn format_input(config: &CliConfig, input: &str) -> Result<String, CliError> {
     match config.mode {
-        CliMode::Columns => {
-            let cols = config.columns.unwrap_or(2);
-            let width = if config.fit_mode {
-                config.width.unwrap_or_else(|| get_terminal_width())
-            } else {
-                config.width.unwrap_or(80) // Fixed width when not in fit mode
-            };
-            let gap = config.gap.unwrap_or(2);
-
-            let layout_config = LayoutConfig {
-                width,
-                gap,
-                padding: 1,
-            };
-
-            // Read input from stdin
-            let input = read_stdin()
-                .map_err(|e| CliError::ProcessingError(format!("Failed to read input: {}", e)))?;
-
-            // Format into columns with optional delimiter
-            let output = format_columns_with_delimiter(&input, cols, &layout_config, config.delimiter.as_deref())
-                .map_err(|e| CliError::ProcessingError(format!("Column formatting failed: {}", e)))?;
-
-            println!("{}", output);
-        }
-        CliMode::Table => {
-            let delimiter = config.delimiter.as_deref().unwrap_or("\t");
-            let width = if config.fit_mode {
-                config.width.unwrap_or_else(|| get_terminal_width())
-            } else {
-                config.width.unwrap_or(80) // Fixed width when not in fit mode
-            };
-
-            // Read input from stdin
-            let input = read_stdin()
-                .map_err(|e| CliError::ProcessingError(format!("Failed to read input: {}", e)))?;
-
-            // Format as table
-            let output = crate::layout::format_table_with_config(&input, delimiter, width)
-                .map_err(|e| CliError::ProcessingError(format!("Table formatting failed: {}", e)))?;
-
-            println!("{}", output);
-        }
-        CliMode::List => {
-            let width = if config.fit_mode {
-                config.width.unwrap_or_else(|| get_terminal_width())
-            } else {
-                config.width.unwrap_or(80) // Fixed width when not in fit mode
-            };
-
-            let list_config = ListConfig {
-                width,
-                line_numbers: config.line_numbers,
-                list_style: config.list_style.clone(),
-                alignment: config.alignment.clone(),
-            };
-
-            // Read input from stdin
-            let input = read_stdin()
-                .map_err(|e| CliError::ProcessingError(format!("Failed to read input: {}", e)))?;
-
-            // Format as list
-            let output = format_list_with_config(&input, &list_config)
-                .map_err(|e| CliError::ProcessingError(format!("List formatting failed: {}", e)))?;
-
-            println!("{}", output);
+        CliMode::Columns => format_as_columns(config, input),
+        CliMode::Table => format_as_table(config, input),
+        CliMode::List => format_as_list(config, input),
+    }
+}
+
+fn format_as_columns(config: &CliConfig, input: &str) -> Result<String, CliError> {
+    let layout_config = LayoutConfig {
+        width: config.layout_width(),
+        gap: config.gap.unwrap_or(2),
+        padding: 1,
+    };
+
+    let cols = config.columns.unwrap_or(2);
+
+    // NUL records take precedence over the delimiter
+    format_columns_with_delimiter(input, cols, &layout_config, config.item_delimiter()).map_err(|e| match e {
+        LayoutError::WidthTooSmall(_, _) | LayoutError::ColumnTooNarrow(_) => {
+            unfit_columns(&split_column_items(input, config.item_delimiter()), cols, layout_config.gap, layout_config.width)
+        }
+        e => e.into(),
+    })
+}
+
+fn format_as_table(config: &CliConfig, input: &str) -> Result<String, CliError> {
+    Ok(format_table_model(&table_model(config, input)?, config.layout_width())?)
+}
+
+/// Parse table input in the configured `--format`, keeping the `--fields` columns
+pub(crate) fn table_model(config: &CliConfig, input: &str) -> Result<TableModel, CliError> {
+    let model = parse_table_with_config(&table_input(config, input), config.input_format, config.table_delimiter(), &config.flatten)?;
+    Ok(select_fields(&model, &config.fields)?)
+}
+
+/// Table text for `input`: NUL-delimited records become one row per line
+pub(crate) fn table_input<'a>(config: &CliConfig, input: &'a str) -> Cow<'a, str> {
+    if !config.null_data {
+        return Cow::Borrowed(input);
+    }
+
+    // Embedded line breaks can't be shown in a row
+    let rows: Vec<String> = split_records(input, &config.stream_config())
+        .iter()
+        .map(|record| record.replace(['\n', '\r'], "?"))
+        .collect();
+    Cow::Owned(rows.join("\n"))
+}
+
+fn format_as_list(config: &CliConfig, input: &str) -> Result<String, CliError> {
+    let list_config = ListConfig {
+        width: config.layout_width(),
+        line_numbers: config.line_numbers,
+        list_style: config.list_style.clone(),
+        alignment: config.alignment.clone(),
+    };
+
+    // Each NUL-delimited record is one item; embedded newlines are shown as `?`
+    if config.null_data {
+        let items = split_records(input, &config.stream_config());
+        return Ok(format_list_items(&items, &list_config)?);
+    }
+
+    // With a delimiter, every delimited field becomes its own item
+    match config.delimiter.as_deref() {
+        Some(delim) if !delim.is_empty() => {
+            let items: Vec<&str> = input
+                .lines()
+                .flat_map(|line| line.split(delim))
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .collect();
+            Ok(format_list_items(&items, &list_config)?)
+        }
+        _ => Ok(format_list_with_config(input, &list_config)?),
+    }
+}
+
+/// Warn on stderr about input that probably doesn't match the options
+///
+/// Only one-shot runs warn; streaming modes would repeat it on every redraw.
+fn warn_about_input(config: &CliConfig, input: &str) {
+    if config.mode == CliMode::Table && !config.null_data && !config.input_format.is_json() {
+        if let Some(warning) = delimiter_warning(input, config.table_delimiter()) {
+            eprintln!("{}", warning);
         }
     }
+}
+
+/// Write rendered output to the `--output` file, or to stdout
+fn emit_output(config: &CliConfig, rendered: &str) -> Result<(), CliError> {
+    let stream = config.stream_config();
+
+    if let Some(path) = &config.output {
+        write_file_atomic(path, &format!("{}\n", rendered), &stream)?;
+        return Ok(());
+    }
+
+    let mut out = OutputWriter::stdout(&stream);
+    out.write_line(rendered)?;
+    out.flush()?;
+    Ok(())
+}
 
+/// Streaming modes redraw continuously: they can't target an output file,
+/// and an explanation per redraw would garble the display
+fn reject_streaming_options(config: &CliConfig, mode: &str) -> Result<(), CliError> {
+    if config.output.is_some() {
+        return Err(CliError::InvalidArgument(format!("--output can't be used with {}", mode)));
+    }
+    if config.explain.is_some() {
+        return Err(CliError::InvalidArgument(format!("--explain can't be used with {}", mode)));
+    }
     Ok(())
-}
\ No newline at end of file
+}
@@ -0,0 +1,41 @@
+//! Config errors per MODULE_SPEC
+
+use std::fmt;
+
+#[derive(Debug, Clone)]
+pub enum ConfigError {
+    /// Config file could not be read (path, reason)
+    ReadError(String, String),
+    /// Malformed config file (path, line, reason)
+    ParseError(String, usize, String),
+    /// Setting rejected by validation (path, key, reason)
+    InvalidSetting(String, String, String),
+    /// Requested profile is not defined (name, defined profiles)
+    UnknownProfile(String, Vec<String>),
+    /// Config file exists and overwriting wasn't requested (path)
+    AlreadyExists(String),
+    /// Config file could not be written (path, reason)
+    WriteError(String, String),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::ReadError(path, msg) => write!(f, "Failed to read config '{}': {}", path, msg),
+            ConfigError::ParseError(path, line, msg) => write!(f, "{}:{}: {}", path, line, msg),
+            ConfigError::InvalidSetting(path, key, msg) => {
+                write!(f, "{}: invalid '{}': {}", path, key, msg)
+            },
+            ConfigError::UnknownProfile(name, defined) if defined.is_empty() => {
+                write!(f, "Unknown profile '{}' (no profiles are defined)", name)
+            },
+            ConfigError::UnknownProfile(name, defined) => {
+                write!(f, "Unknown profile '{}' (defined: {})", name, defined.join(", "))
+            },
+            ConfigError::AlreadyExists(path) => write!(f, "Config file '{}' already exists", path),
+            ConfigError::WriteError(path, msg) => write!(f, "Failed to write config '{}': {}", path, msg),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
@@ -0,0 +1,203 @@
+//! Config internal helpers per MODULE_SPEC
+//!
+//! Config files use a small subset of TOML: `key = value` lines, `[section]`
+//! headers and `#` comments. Values are strings (basic or literal), integers
+//! or booleans; that covers every rolo setting without a TOML dependency.
+
+/// Table a config entry belongs to
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Section {
+    /// Top-level settings before any header
+    Top,
+    /// `[profile.NAME]`
+    Profile(String),
+    /// Any other table (e.g. `[plugins]`), ignored by rolo today
+    Other,
+}
+
+/// One `key = value` line of a config file
+#[derive(Debug, Clone)]
+pub(crate) struct Entry {
+    pub section: Section,
+    pub key: String,
+    pub value: String,
+    pub line: usize,
+}
+
+/// Parse config text into entries, failing with `(line, reason)`
+///
+/// Entries of tables other than the top level and profiles are skipped
+/// without validating their values.
+pub(crate) fn parse_config_text(text: &str) -> Result<Vec<Entry>, (usize, String)> {
+    let mut entries = Vec::new();
+    let mut section = Section::Top;
+
+    for (index, raw) in text.lines().enumerate() {
+        let line = index + 1;
+        let trimmed = raw.trim();
+
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        if trimmed.starts_with('[') {
+            section = parse_header(trimmed).map_err(|msg| (line, msg))?;
+            continue;
+        }
+
+        if section == Section::Other {
+            continue;
+        }
+
+        let (key, value) = trimmed
+            .split_once('=')
+            .ok_or_else(|| (line, "expected 'key = value'".to_string()))?;
+        let key = unquote_key(key.trim()).map_err(|msg| (line, msg))?;
+        let value = parse_value(value.trim()).map_err(|msg| (line, msg))?;
+
+        entries.push(Entry { section: section.clone(), key, value, line });
+    }
+
+    Ok(entries)
+}
+
+/// Parse a `[table]` header
+fn parse_header(header: &str) -> Result<Section, String> {
+    // Arrays of tables are never rolo settings
+    if header.starts_with("[[") {
+        return Ok(Section::Other);
+    }
+
+    let end = header.find(']').ok_or_else(|| "unterminated table header".to_string())?;
+    ensure_trailing_comment(&header[end + 1..])?;
+
+    let name = header[1..end].trim();
+    match name.split_once('.') {
+        Some((table, profile)) if table.trim() == "profile" => {
+            let profile = unquote_key(profile.trim())?;
+            if profile.is_empty() {
+                return Err("profile name can't be empty".to_string());
+            }
+            Ok(Section::Profile(profile))
+        }
+        _ => Ok(Section::Other),
+    }
+}
+
+/// Bare or quoted key; dashes are accepted as underscores
+fn unquote_key(key: &str) -> Result<String, String> {
+    let unquoted = if key.len() >= 2 && (key.starts_with('"') && key.ends_with('"') || key.starts_with('\'') && key.ends_with('\'')) {
+        &key[1..key.len() - 1]
+    } else {
+        key
+    };
+
+    if unquoted.is_empty() {
+        return Err("missing key before '='".to_string());
+    }
+    Ok(unquoted.replace('-', "_"))
+}
+
+/// Parse a value: quoted string, integer or boolean, with an optional comment
+fn parse_value(raw: &str) -> Result<String, String> {
+    if let Some(rest) = raw.strip_prefix('"') {
+        return parse_basic_string(rest);
+    }
+
+    if let Some(rest) = raw.strip_prefix('\'') {
+        let end = rest.find('\'').ok_or_else(|| "unterminated string".to_string())?;
+        ensure_trailing_comment(&rest[end + 1..])?;
+        return Ok(rest[..end].to_string());
+    }
+
+    let bare = raw.split('#').next().unwrap_or("").trim();
+    if bare.is_empty() {
+        return Err("missing value after '='".to_string());
+    }
+    if bare.starts_with('[') || bare.starts_with('{') {
+        return Err("arrays and inline tables are not supported".to_string());
+    }
+
+    let is_integer = bare.trim_start_matches(['+', '-']).chars().all(|c| c.is_ascii_digit() || c == '_');
+    if bare == "true" || bare == "false" || is_integer {
+        Ok(bare.replace('_', ""))
+    } else {
+        Err(format!("invalid value '{}' (strings must be quoted)", bare))
+    }
+}
+
+/// Parse the rest of a `"..."` string, handling TOML escapes
+fn parse_basic_string(rest: &str) -> Result<String, String> {
+    let mut value = String::new();
+    let mut chars = rest.char_indices();
+
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '"' => {
+                ensure_trailing_comment(&rest[index + 1..])?;
+                return Ok(value);
+            }
+            '\\' => {
+                let escaped = match chars.next().map(|(_, c)| c) {
+                    Some('n') => '\n',
+                    Some('t') => '\t',
+                    Some('r') => '\r',
+                    Some('b') => '\u{8}',
+                    Some('f') => '\u{c}',
+                    Some('"') => '"',
+                    Some('\\') => '\\',
+                    Some(kind @ ('u' | 'U')) => {
+                        let len = if kind == 'u' { 4 } else { 8 };
+                        let hex: String = chars.by_ref().take(len).map(|(_, c)| c).collect();
+                        u32::from_str_radix(&hex, 16)
+                            .ok()
+                            .filter(|_| hex.len() == len)
+                            .and_then(char::from_u32)
+                            .ok_or_else(|| format!("invalid unicode escape '\\{}{}'", kind, hex))?
+                    }
+                    Some(other) => return Err(format!("invalid escape '\\{}'", other)),
+                    None => return Err("unterminated string".to_string()),
+                };
+                value.push(escaped);
+            }
+            _ => value.push(c),
+        }
+    }
+
+    Err("unterminated string".to_string())
+}
+
+/// Only whitespace or a comment may follow a value or header
+fn ensure_trailing_comment(rest: &str) -> Result<(), String> {
+    let rest = rest.trim();
+    if rest.is_empty() || rest.starts_with('#') {
+        Ok(())
+    } else {
+        Err(format!("unexpected text '{}'", rest))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_values_and_sections() {
+        let text = "mode = \"table\" # comment\ngap = 3\n\n[plugins.csv]\nquote = '\"'\n\n[profile.wide]\ndelimiter = \"\\t\"\nline-numbers = true\n";
+        let entries = parse_config_text(text).unwrap();
+
+        assert_eq!(entries.len(), 4);
+        assert_eq!((entries[0].key.as_str(), entries[0].value.as_str()), ("mode", "table"));
+        assert_eq!(entries[1].value, "3");
+        assert_eq!(entries[2].section, Section::Profile("wide".to_string()));
+        assert_eq!(entries[2].value, "\t");
+        assert_eq!((entries[3].key.as_str(), entries[3].line), ("line_numbers", 9));
+    }
+
+    #[test]
+    fn test_parse_errors_report_line() {
+        assert_eq!(parse_config_text("gap = 2\nmode = table\n").unwrap_err().0, 2);
+        assert!(parse_config_text("delimiter = \"|").is_err());
+        assert!(parse_config_text("[profile.x\n").is_err());
+    }
+}
@@ -0,0 +1,10 @@
+//! Config module orchestrator - Config files and named profiles
+//! Follows RSB MODULE_SPEC patterns
+
+mod helpers; // Internal implementation details per MODULE_SPEC
+
+pub mod error;
+pub mod utils;
+
+// Re-export public APIs
+pub use utils::*;
@@ -0,0 +1,440 @@
+//! Config utilities - public API per MODULE_SPEC
+//!
+//! Settings are layered, lowest first: built-in defaults, the user config
+//! (`$XDG_CONFIG_HOME/rolo/config.toml`, usually `~/.config/rolo/`), the
+//! nearest project `.rolo.toml` found from the working directory upward, the
+//! `[profile.NAME]` tables selected with `--profile`, `ROLO_*` environment
+//! variables, and finally command line flags. Every value goes through the
+//! same validation as its CLI flag.
+
+use crate::cli::error::CliError;
+use crate::cli::utils::parse_into;
+use crate::cli::options::{OptionAction, OPTIONS};
+use crate::cli::{apply_setting, parse_args, CliConfig};
+use crate::config::error::ConfigError;
+use crate::config::helpers::{parse_config_text, Section};
+use crate::stream::{ColorPolicy, LineEnding};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Settings accepted in config files, in `rolo config show` order
+pub const CONFIG_KEYS: &[&str] = &[
+    "mode", "cols", "width", "gap", "delimiter", "format", "max_depth", "arrays", "fields",
+    "alignment", "list_style", "line_numbers", "fit", "color", "encoding", "eol", "interval",
+];
+
+/// Environment variables and the settings they override
+///
+/// Per-invocation options ([`NO_ENV_OPTIONS`], the subcommand) have no
+/// variable on purpose: they don't make sense as ambient defaults.
+pub const ENV_VARS: &[(&str, &str)] = &[
+    ("ROLO_MODE", "mode"),
+    ("ROLO_COLS", "cols"),
+    ("ROLO_WIDTH", "width"),
+    ("ROLO_GAP", "gap"),
+    ("ROLO_DELIM", "delimiter"),
+    ("ROLO_FORMAT", "format"),
+    ("ROLO_MAX_DEPTH", "max_depth"),
+    ("ROLO_ARRAYS", "arrays"),
+    ("ROLO_FIELDS", "fields"),
+    ("ROLO_ALIGN", "alignment"),
+    ("ROLO_LIST_STYLE", "list_style"),
+    ("ROLO_LINE_NUMBERS", "line_numbers"),
+    ("ROLO_FIT", "fit"),
+    ("ROLO_COLOR", "color"),
+    ("ROLO_ENCODING", "encoding"),
+    ("ROLO_EOL", "eol"),
+    ("ROLO_INTERVAL", "interval"),
+    ("ROLO_NULL_DATA", "null_data"),
+    ("ROLO_BINARY_OK", "binary_ok"),
+    ("ROLO_PRE", "pre"),
+    ("ROLO_POST", "post"),
+    ("ROLO_ERROR_FORMAT", "error_format"),
+];
+
+/// Options deliberately left without an environment variable
+pub const NO_ENV_OPTIONS: &[&str] = &["output", "follow", "explain"];
+
+/// Environment variable selecting a profile when `--profile` isn't given
+pub const PROFILE_ENV_VAR: &str = "ROLO_PROFILE";
+
+/// File name of project-local config files
+pub const PROJECT_CONFIG_NAME: &str = ".rolo.toml";
+
+/// A parsed config file
+#[derive(Debug, Clone)]
+pub struct ConfigFile {
+    pub path: PathBuf,
+    /// Top-level settings as `(key, value)`
+    pub settings: Vec<(String, String)>,
+    /// `[profile.NAME]` tables in file order
+    pub profiles: Vec<(String, Vec<(String, String)>)>,
+}
+
+impl ConfigFile {
+    /// Read and parse a config file
+    pub fn load(path: &Path) -> Result<Self, ConfigError> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| ConfigError::ReadError(path.display().to_string(), e.to_string()))?;
+        Self::parse(path, &text)
+    }
+
+    /// Parse config text; `path` is used for error messages and sources
+    pub fn parse(path: &Path, text: &str) -> Result<Self, ConfigError> {
+        let shown = path.display().to_string();
+        let entries = parse_config_text(text).map_err(|(line, msg)| ConfigError::ParseError(shown.clone(), line, msg))?;
+
+        let mut file = ConfigFile { path: path.to_path_buf(), settings: Vec::new(), profiles: Vec::new() };
+        for entry in entries {
+            if !CONFIG_KEYS.contains(&entry.key.as_str()) {
+                let msg = format!("unknown setting '{}' (expected one of: {})", entry.key, CONFIG_KEYS.join(", "));
+                return Err(ConfigError::ParseError(shown, entry.line, msg));
+            }
+
+            let setting = (entry.key, entry.value);
+            match entry.section {
+                Section::Profile(name) => match file.profiles.iter_mut().find(|(existing, _)| *existing == name) {
+                    Some((_, settings)) => settings.push(setting),
+                    None => file.profiles.push((name, vec![setting])),
+                },
+                _ => file.settings.push(setting),
+            }
+        }
+
+        Ok(file)
+    }
+
+    /// Settings of a named profile, if this file defines it
+    pub fn profile(&self, name: &str) -> Option<&[(String, String)]> {
+        self.profiles
+            .iter()
+            .find(|(profile, _)| profile == name)
+            .map(|(_, settings)| settings.as_slice())
+    }
+}
+
+/// Where an effective setting came from
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValueSource {
+    Default,
+    /// Top-level setting of a config file
+    File(PathBuf),
+    /// `[profile.NAME]` table of a config file
+    Profile(String, PathBuf),
+    /// `ROLO_*` environment variable
+    Env(String),
+    /// Command line flag
+    Cli,
+}
+
+impl fmt::Display for ValueSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValueSource::Default => write!(f, "default"),
+            ValueSource::File(path) => write!(f, "{}", path.display()),
+            ValueSource::Profile(name, path) => write!(f, "profile '{}' ({})", name, path.display()),
+            ValueSource::Env(var) => write!(f, "env {}", var),
+            ValueSource::Cli => write!(f, "command line"),
+        }
+    }
+}
+
+/// Effective configuration with the source of each setting
+#[derive(Debug, Clone)]
+pub struct ResolvedConfig {
+    pub config: CliConfig,
+    /// Config files that were loaded, lowest precedence first
+    pub files: Vec<PathBuf>,
+    sources: Vec<(String, ValueSource)>,
+}
+
+impl ResolvedConfig {
+    /// Where a setting's effective value came from
+    pub fn source(&self, key: &str) -> ValueSource {
+        self.sources
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, source)| source.clone())
+            .unwrap_or(ValueSource::Default)
+    }
+
+    fn record(&mut self, key: &str, source: ValueSource) {
+        match self.sources.iter_mut().find(|(k, _)| k == key) {
+            Some((_, existing)) => *existing = source,
+            None => self.sources.push((key.to_string(), source)),
+        }
+    }
+}
+
+impl From<CliConfig> for ResolvedConfig {
+    /// A configuration that wasn't layered over config files
+    fn from(config: CliConfig) -> Self {
+        ResolvedConfig { config, files: Vec::new(), sources: Vec::new() }
+    }
+}
+
+/// User config path: `$XDG_CONFIG_HOME/rolo/config.toml`, else `~/.config/rolo/config.toml`
+pub fn user_config_path() -> Option<PathBuf> {
+    let base = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|dir| dir.is_absolute())
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(base.join("rolo").join("config.toml"))
+}
+
+/// Nearest `.rolo.toml` in `start` or one of its parents
+pub fn find_project_config(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .map(|dir| dir.join(PROJECT_CONFIG_NAME))
+        .find(|path| path.is_file())
+}
+
+/// Existing config files, lowest precedence first
+pub fn config_paths() -> Vec<PathBuf> {
+    let user = user_config_path().filter(|path| path.is_file());
+    let project = std::env::current_dir().ok().and_then(|dir| find_project_config(&dir));
+    user.into_iter().chain(project).collect()
+}
+
+/// Set, non-empty `ROLO_*` variables of the process environment as `(name, value)`
+pub fn env_overrides() -> Vec<(String, String)> {
+    ENV_VARS
+        .iter()
+        .map(|(var, _)| *var)
+        .chain(std::iter::once(PROFILE_ENV_VAR))
+        .filter_map(|var| std::env::var(var).ok().map(|value| (var.to_string(), value)))
+        .filter(|(_, value)| !value.is_empty())
+        .collect()
+}
+
+/// Parse `args` over the config files and environment of this process
+pub fn resolve_config(args: &[String]) -> Result<ResolvedConfig, CliError> {
+    resolve_config_with(args, &config_paths(), &env_overrides())
+}
+
+/// Parse `args` layered over the given config files (lowest precedence first)
+pub fn resolve_config_from(args: &[String], paths: &[PathBuf]) -> Result<ResolvedConfig, CliError> {
+    resolve_config_with(args, paths, &[])
+}
+
+/// Parse `args` layered over config files and `ROLO_*` variables given as `(name, value)`
+///
+/// Invalid variable values fail with the same error as the equivalent flag.
+pub fn resolve_config_with(args: &[String], paths: &[PathBuf], env: &[(String, String)]) -> Result<ResolvedConfig, CliError> {
+    // First pass validates the flags and finds the profile
+    let cli = parse_args(args)?;
+    if cli.help || cli.version || matches!(cli.subcommand.as_deref(), Some("help") | Some("version")) {
+        // Help must work even when a config file is broken
+        return Ok(cli.into());
+    }
+
+    let files = paths
+        .iter()
+        .map(|path| ConfigFile::load(path))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut resolved = ResolvedConfig::from(CliConfig::default());
+    resolved.files = paths.to_vec();
+
+    for file in &files {
+        for (key, value) in &file.settings {
+            apply_file_setting(&mut resolved.config, &file.path, key, value)?;
+            resolved.record(key, ValueSource::File(file.path.clone()));
+        }
+    }
+
+    let env_profile = env.iter().find(|(var, _)| var == PROFILE_ENV_VAR).map(|(_, name)| name.clone());
+    let profile = cli.profile.clone().or(env_profile);
+
+    if let Some(name) = &profile {
+        let mut found = false;
+        for file in &files {
+            if let Some(settings) = file.profile(name) {
+                found = true;
+                for (key, value) in settings {
+                    apply_file_setting(&mut resolved.config, &file.path, key, value)?;
+                    resolved.record(key, ValueSource::Profile(name.clone(), file.path.clone()));
+                }
+            }
+        }
+        if !found {
+            let mut defined: Vec<String> = files
+                .iter()
+                .flat_map(|file| file.profiles.iter().map(|(profile, _)| profile.clone()))
+                .collect();
+            defined.sort();
+            defined.dedup();
+            return Err(ConfigError::UnknownProfile(name.clone(), defined).into());
+        }
+    }
+    resolved.config.profile = profile;
+
+    for (var, value) in env {
+        if let Some((_, key)) = ENV_VARS.iter().find(|(name, _)| name == var) {
+            apply_setting(&mut resolved.config, key, value)?;
+            resolved.record(key, ValueSource::Env(var.clone()));
+        }
+    }
+
+    // Command line flags win over everything from files
+    let mut set_keys = Vec::new();
+    parse_into(&mut resolved.config, args, &mut set_keys)?;
+    for key in set_keys {
+        resolved.record(key, ValueSource::Cli);
+    }
+    resolved.config.width_source = resolved.source("width");
+
+    // Filter commands accumulate, so flags replace ROLO_PRE/ROLO_POST rather than adding to them
+    if !cli.pre_commands.is_empty() {
+        resolved.config.pre_commands = cli.pre_commands;
+    }
+    if !cli.post_commands.is_empty() {
+        resolved.config.post_commands = cli.post_commands;
+    }
+
+    Ok(resolved)
+}
+
+/// Effective value of a setting as shown by `rolo config show`
+pub fn setting_value(config: &CliConfig, key: &str) -> String {
+    let auto = |value: Option<usize>| value.map_or_else(|| "auto".to_string(), |n| n.to_string());
+
+    match key {
+        "mode" => config.mode.as_str().to_string(),
+        "cols" => auto(config.columns),
+        "width" => auto(config.width),
+        "gap" => auto(config.gap),
+        "delimiter" => config.delimiter.as_ref().map_or_else(|| "auto".to_string(), |d| format!("{:?}", d)),
+        "format" => config.input_format.as_str().to_string(),
+        "max_depth" => config.flatten.max_depth.map_or_else(|| "all".to_string(), |n| n.to_string()),
+        "arrays" => config.flatten.arrays.as_str().to_string(),
+        "fields" if config.fields.is_empty() => "all".to_string(),
+        "fields" => config.fields.join(","),
+        "alignment" => format!("{:?}", config.alignment).to_lowercase(),
+        "list_style" => config.list_style.clone().unwrap_or_else(|| "auto".to_string()),
+        "line_numbers" => config.line_numbers.to_string(),
+        "fit" => config.fit_mode.to_string(),
+        "color" => match config.color {
+            ColorPolicy::Auto => "auto",
+            ColorPolicy::Always => "always",
+            ColorPolicy::Never => "never",
+        }
+        .to_string(),
+        "encoding" => format!("{:?}", config.encoding).to_lowercase(),
+        "eol" => match config.line_ending {
+            LineEnding::Unix => "lf",
+            LineEnding::Windows => "crlf",
+            LineEnding::Mac => "cr",
+            LineEnding::Nul => "nul",
+        }
+        .to_string(),
+        "interval" => format!("{:?}", config.interval),
+        _ => String::new(),
+    }
+}
+
+/// Render the effective settings and their sources for `rolo config show`
+pub fn format_config_show(resolved: &ResolvedConfig) -> String {
+    let mut out = String::new();
+
+    if resolved.files.is_empty() {
+        out.push_str("Config files: none\n");
+    } else {
+        out.push_str("Config files:\n");
+        for path in &resolved.files {
+            out.push_str(&format!("  {}\n", path.display()));
+        }
+    }
+    out.push_str(&format!("Profile: {}\n\n", resolved.config.profile.as_deref().unwrap_or("none")));
+
+    let values: Vec<String> = CONFIG_KEYS.iter().map(|key| setting_value(&resolved.config, key)).collect();
+    let key_width = CONFIG_KEYS.iter().map(|key| key.len()).max().unwrap_or(0);
+    let value_width = values.iter().map(|value| value.chars().count()).max().unwrap_or(0);
+
+    for (key, value) in CONFIG_KEYS.iter().zip(&values) {
+        let line = format!("{:<kw$}  {:<vw$}  {}", key, value, resolved.source(key), kw = key_width, vw = value_width);
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+
+    out
+}
+
+/// Example values of the commented-out settings in [`starter_config`]
+const STARTER_VALUES: &[(&str, &str)] = &[
+    ("mode", "\"columns\""),
+    ("cols", "3"),
+    ("width", "100"),
+    ("gap", "2"),
+    ("delimiter", "\",\""),
+    ("format", "\"csv\""),
+    ("max_depth", "2"),
+    ("arrays", "\"join\""),
+    ("fields", "\"name,user.city\""),
+    ("alignment", "\"left\""),
+    ("list_style", "\"bullets\""),
+    ("line_numbers", "true"),
+    ("fit", "true"),
+    ("color", "\"auto\""),
+    ("encoding", "\"utf8\""),
+    ("eol", "\"lf\""),
+    ("interval", "\"2s\""),
+];
+
+/// Commented starter config: every setting with its description, all disabled
+pub fn starter_config() -> String {
+    let mut text = String::from(concat!(
+        "# rolo config - written by `rolo init`\n",
+        "#\n",
+        "# Uncomment a setting to make it the default. A .rolo.toml in a project\n",
+        "# overrides this file, ROLO_* variables override both, and command line\n",
+        "# options always win. Check the result with `rolo config show`.\n",
+    ));
+
+    for key in CONFIG_KEYS {
+        let value = STARTER_VALUES.iter().find(|(k, _)| k == key).map_or("\"\"", |(_, value)| *value);
+        text.push_str(&format!("\n# {}\n# {} = {}\n", setting_summary(key), key, value));
+    }
+
+    text.push_str(concat!(
+        "\n",
+        "# Named profiles, selected with --profile NAME or ROLO_PROFILE\n",
+        "# [profile.wide]\n",
+        "# width = 160\n",
+        "# cols = 4\n",
+    ));
+    text
+}
+
+/// Description of a setting, from the option that sets it
+fn setting_summary(key: &str) -> String {
+    if key == "mode" {
+        return "Layout mode (columns, table or list; default columns)".to_string();
+    }
+    OPTIONS
+        .iter()
+        .find(|option| matches!(option.action, OptionAction::Set(k) | OptionAction::Fixed(k, "true") if k == key))
+        .map_or_else(|| key.replace('_', " "), |option| option.summary())
+}
+
+/// Write [`starter_config`] to `path`, creating its directory
+///
+/// An existing file is only replaced when `force` is set.
+pub fn write_starter_config(path: &Path, force: bool) -> Result<(), ConfigError> {
+    let shown = path.display().to_string();
+    if path.exists() && !force {
+        return Err(ConfigError::AlreadyExists(shown));
+    }
+
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| ConfigError::WriteError(shown.clone(), e.to_string()))?;
+    }
+    std::fs::write(path, starter_config()).map_err(|e| ConfigError::WriteError(shown, e.to_string()))
+}
+
+/// Apply a config file setting, reporting failures against the file
+fn apply_file_setting(config: &mut CliConfig, path: &Path, key: &str, value: &str) -> Result<(), ConfigError> {
+    apply_setting(config, key, value)
+        .map_err(|e| ConfigError::InvalidSetting(path.display().to_string(), key.to_string(), e.to_string()))
+}
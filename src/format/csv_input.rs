@@ -0,0 +1,28 @@
+//! RFC 4180 CSV records via the `csv` crate (csv-support feature)
+
+use crate::format::error::FormatError;
+use crate::format::helpers::single_line;
+
+/// Parse CSV text into records
+///
+/// Quoted fields may contain the delimiter, doubled quotes and line breaks;
+/// line breaks are shown as `↵`. Fields are trimmed like delimited cells,
+/// blank lines are skipped and rows may have different lengths.
+pub(crate) fn parse_csv_records(text: &str, delimiter: u8) -> Result<Vec<Vec<String>>, FormatError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .delimiter(delimiter)
+        .trim(csv::Trim::Fields)
+        .from_reader(text.as_bytes());
+
+    let mut records = Vec::new();
+    for record in reader.records() {
+        let record = record.map_err(|e| FormatError::Malformed("CSV".to_string(), e.to_string()))?;
+        if record.len() == 1 && record[0].is_empty() {
+            continue;
+        }
+        records.push(record.iter().map(single_line).collect());
+    }
+    Ok(records)
+}
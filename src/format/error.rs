@@ -0,0 +1,39 @@
+//! Input format errors per MODULE_SPEC
+
+use std::fmt;
+
+#[derive(Debug, Clone)]
+pub enum FormatError {
+    /// Format needs a cargo feature this build lacks (format, feature)
+    Unsupported(String, String),
+    /// Delimiter can't be used with the format (format, delimiter)
+    InvalidDelimiter(String, String),
+    /// Input isn't valid in the format (format, reason)
+    Malformed(String, String),
+    /// A selected field matches no column (field, columns)
+    UnknownField(String, Vec<String>),
+    /// Two values of one object flatten to the same column (column, object number)
+    DuplicateColumn(String, usize),
+}
+
+impl fmt::Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FormatError::Unsupported(format, feature) => {
+                write!(f, "{} input needs rolo built with the '{}' feature", format, feature)
+            }
+            FormatError::InvalidDelimiter(format, delimiter) => {
+                write!(f, "{} delimiter must be a single ASCII character, got {:?}", format, delimiter)
+            }
+            FormatError::Malformed(format, reason) => write!(f, "Invalid {} input: {}", format, reason),
+            FormatError::UnknownField(field, columns) => {
+                write!(f, "No column matches field '{}' (columns: {})", field, columns.join(", "))
+            }
+            FormatError::DuplicateColumn(column, object) => {
+                write!(f, "Object {} has two values for column '{}': a key containing '.' or '[' matches a flattened path", object, column)
+            }
+        }
+    }
+}
+
+impl std::error::Error for FormatError {}
@@ -0,0 +1,10 @@
+//! Input format internal helpers per MODULE_SPEC
+
+/// Show line breaks inside a field as `↵` so the cell stays on one table row
+pub(crate) fn single_line(field: &str) -> String {
+    if field.contains(['\n', '\r']) {
+        field.replace("\r\n", "↵").replace(['\n', '\r'], "↵")
+    } else {
+        field.to_string()
+    }
+}
@@ -0,0 +1,236 @@
+//! JSON and JSON Lines records via `serde_json` (json-support feature)
+//!
+//! Objects are read into [`JsonValue`] rather than `serde_json::Value`,
+//! whose map sorts keys: table headers follow the order keys first appear.
+//! Nested values are then flattened into `(path, cell)` pairs.
+
+use crate::format::error::FormatError;
+use crate::format::helpers::single_line;
+use crate::format::utils::{ArrayStyle, FlattenConfig};
+use serde::de::{Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+/// A JSON value with object keys kept in input order
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum JsonValue {
+    Null,
+    Bool(bool),
+    /// Numbers as written by `serde_json`, so large integers stay exact
+    Number(String),
+    String(String),
+    Array(Vec<JsonValue>),
+    Object(Vec<(String, JsonValue)>),
+}
+
+impl JsonValue {
+    /// Text of a table cell: strings unquoted, null empty, containers as compact JSON
+    pub(crate) fn cell(&self) -> String {
+        match self {
+            JsonValue::Null => String::new(),
+            JsonValue::String(text) => single_line(text),
+            value => value.compact(),
+        }
+    }
+
+    /// Compact JSON text, e.g. `["a",1]` or `{"k":true}`
+    pub(crate) fn compact(&self) -> String {
+        match self {
+            JsonValue::Null => "null".to_string(),
+            JsonValue::Bool(value) => value.to_string(),
+            JsonValue::Number(number) => number.clone(),
+            JsonValue::String(text) => serde_json::to_string(text).unwrap_or_default(),
+            JsonValue::Array(items) => {
+                let items: Vec<String> = items.iter().map(JsonValue::compact).collect();
+                format!("[{}]", items.join(","))
+            }
+            JsonValue::Object(entries) => {
+                let entries: Vec<String> = entries
+                    .iter()
+                    .map(|(key, value)| format!("{}:{}", JsonValue::String(key.clone()).compact(), value.compact()))
+                    .collect();
+                format!("{{{}}}", entries.join(","))
+            }
+        }
+    }
+
+    fn kind(&self) -> &'static str {
+        match self {
+            JsonValue::Null => "null",
+            JsonValue::Bool(_) => "a boolean",
+            JsonValue::Number(_) => "a number",
+            JsonValue::String(_) => "a string",
+            JsonValue::Array(_) => "an array",
+            JsonValue::Object(_) => "an object",
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for JsonValue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(JsonValueVisitor)
+    }
+}
+
+struct JsonValueVisitor;
+
+impl<'de> Visitor<'de> for JsonValueVisitor {
+    type Value = JsonValue;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a JSON value")
+    }
+
+    fn visit_unit<E>(self) -> Result<JsonValue, E> {
+        Ok(JsonValue::Null)
+    }
+
+    fn visit_bool<E>(self, value: bool) -> Result<JsonValue, E> {
+        Ok(JsonValue::Bool(value))
+    }
+
+    fn visit_i64<E>(self, value: i64) -> Result<JsonValue, E> {
+        Ok(JsonValue::Number(value.to_string()))
+    }
+
+    fn visit_u64<E>(self, value: u64) -> Result<JsonValue, E> {
+        Ok(JsonValue::Number(value.to_string()))
+    }
+
+    fn visit_f64<E>(self, value: f64) -> Result<JsonValue, E> {
+        // serde_json's own formatting keeps `1.0` and `1e300` short
+        let text = serde_json::Number::from_f64(value).map_or_else(|| value.to_string(), |number| number.to_string());
+        Ok(JsonValue::Number(text))
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<JsonValue, E> {
+        Ok(JsonValue::String(value.to_string()))
+    }
+
+    fn visit_string<E>(self, value: String) -> Result<JsonValue, E> {
+        Ok(JsonValue::String(value))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<JsonValue, A::Error> {
+        let mut items = Vec::new();
+        while let Some(item) = seq.next_element()? {
+            items.push(item);
+        }
+        Ok(JsonValue::Array(items))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<JsonValue, A::Error> {
+        let mut entries: Vec<(String, JsonValue)> = Vec::new();
+        while let Some((key, value)) = map.next_entry::<String, JsonValue>()? {
+            // Like serde_json, a repeated key keeps its last value
+            match entries.iter_mut().find(|(k, _)| *k == key) {
+                Some(entry) => entry.1 = value,
+                None => entries.push((key, value)),
+            }
+        }
+        Ok(JsonValue::Object(entries))
+    }
+}
+
+fn malformed(reason: String) -> FormatError {
+    FormatError::Malformed("JSON".to_string(), reason)
+}
+
+/// Parse a JSON array of objects (or a single object) into objects
+pub(crate) fn parse_json_objects(text: &str) -> Result<Vec<Vec<(String, JsonValue)>>, FormatError> {
+    if text.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let value: JsonValue = serde_json::from_str(text).map_err(|e| malformed(e.to_string()))?;
+    match value {
+        JsonValue::Array(items) => items
+            .into_iter()
+            .enumerate()
+            .map(|(i, item)| match item {
+                JsonValue::Object(entries) => Ok(entries),
+                other => Err(malformed(format!("item {} is {}, expected an object", i + 1, other.kind()))),
+            })
+            .collect(),
+        JsonValue::Object(entries) => Ok(vec![entries]),
+        other => Err(malformed(format!("input is {}, expected an array of objects", other.kind()))),
+    }
+}
+
+/// Parse JSON Lines: one object per line, blank lines skipped
+pub(crate) fn parse_json_lines(text: &str) -> Result<Vec<Vec<(String, JsonValue)>>, FormatError> {
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            let value: JsonValue = serde_json::from_str(line).map_err(|e| malformed(format!("line {}: {}", i + 1, e)))?;
+            match value {
+                JsonValue::Object(entries) => Ok(entries),
+                other => Err(malformed(format!("line {} is {}, expected an object", i + 1, other.kind()))),
+            }
+        })
+        .collect()
+}
+
+/// Cells of one object as `(column path, text)`, nested values flattened
+///
+/// A key such as `"a.b"` next to `{"a": {"b": ...}}` would give two cells the
+/// same column; that's an error rather than one value silently hiding the other.
+pub(crate) fn flatten_object(entries: &[(String, JsonValue)], config: &FlattenConfig) -> Result<Vec<(String, String)>, String> {
+    let mut cells: Vec<(String, String)> = Vec::new();
+    for (key, value) in entries {
+        flatten_value(key.clone(), value, config.max_depth, config, &mut cells);
+    }
+    let mut seen: HashSet<&str> = HashSet::with_capacity(cells.len());
+    if let Some((path, _)) = cells.iter().find(|(path, _)| !seen.insert(path.as_str())) {
+        return Err(path.clone());
+    }
+    Ok(cells)
+}
+
+fn flatten_value(path: String, value: &JsonValue, depth: Option<usize>, config: &FlattenConfig, cells: &mut Vec<(String, String)>) {
+    let deeper = depth.map(|depth| depth.saturating_sub(1));
+    match value {
+        JsonValue::Object(entries) if !entries.is_empty() && depth != Some(0) => {
+            for (key, value) in entries {
+                flatten_value(format!("{}.{}", path, key), value, deeper, config, cells);
+            }
+        }
+        JsonValue::Array(items) if !items.is_empty() && depth != Some(0) && config.arrays == ArrayStyle::Index => {
+            for (i, item) in items.iter().enumerate() {
+                flatten_value(format!("{}[{}]", path, i), item, deeper, config, cells);
+            }
+        }
+        JsonValue::Array(items) if config.arrays == ArrayStyle::Join => {
+            let joined: Vec<String> = items.iter().map(JsonValue::cell).collect();
+            cells.push((path, joined.join(", ")));
+        }
+        value => cells.push((path, value.cell())),
+    }
+}
+
+/// Header record (the union of columns, first seen first) followed by one record per object
+///
+/// Columns an object lacks become empty cells.
+pub(crate) fn object_records(objects: &[Vec<(String, String)>]) -> Vec<Vec<String>> {
+    let mut headers: Vec<&str> = Vec::new();
+    let mut columns: HashMap<&str, usize> = HashMap::new();
+    for (path, _) in objects.iter().flatten() {
+        columns.entry(path.as_str()).or_insert_with(|| {
+            headers.push(path);
+            headers.len() - 1
+        });
+    }
+    if headers.is_empty() {
+        return Vec::new();
+    }
+
+    let rows = objects.iter().map(|cells| {
+        let mut row = vec![String::new(); headers.len()];
+        for (path, cell) in cells {
+            row[columns[path.as_str()]] = cell.clone();
+        }
+        row
+    });
+    std::iter::once(headers.iter().map(|header| single_line(header)).collect()).chain(rows).collect()
+}
@@ -0,0 +1,16 @@
+//! Input format module orchestrator - structured table input (CSV, JSON)
+//! Follows RSB MODULE_SPEC patterns
+
+mod helpers; // Internal implementation details per MODULE_SPEC
+
+pub mod error;
+pub mod utils;
+
+// Format parsers (feature-gated)
+#[cfg(feature = "csv-support")]
+mod csv_input;
+#[cfg(feature = "json-support")]
+mod json_input;
+
+// Re-export public APIs
+pub use utils::*;
@@ -0,0 +1,210 @@
+//! Input format utilities - public API per MODULE_SPEC
+//!
+//! Table mode reads plain delimited text by default, splitting every line on
+//! the delimiter. Structured formats (CSV, JSON) are parsed into the same
+//! [`TableModel`] so they render identically; each needs its cargo feature.
+
+use crate::format::error::FormatError;
+use crate::layout::TableModel;
+
+/// Input format of table mode
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InputFormat {
+    /// CSV for a `,` delimiter when CSV support is built in, else delimited
+    Auto,
+    /// One row per line, cells split on the delimiter
+    Delimited,
+    /// RFC 4180 CSV: quoted fields, doubled quotes and embedded line breaks
+    Csv,
+    /// A JSON array of objects, one row per object
+    Json,
+    /// JSON Lines: one object per line
+    Jsonl,
+}
+
+impl InputFormat {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "auto" => Some(InputFormat::Auto),
+            "delimited" => Some(InputFormat::Delimited),
+            "csv" => Some(InputFormat::Csv),
+            "json" => Some(InputFormat::Json),
+            "jsonl" => Some(InputFormat::Jsonl),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            InputFormat::Auto => "auto",
+            InputFormat::Delimited => "delimited",
+            InputFormat::Csv => "csv",
+            InputFormat::Json => "json",
+            InputFormat::Jsonl => "jsonl",
+        }
+    }
+
+    /// Formats made of objects, whose keys name the columns
+    pub fn is_json(&self) -> bool {
+        matches!(self, InputFormat::Json | InputFormat::Jsonl)
+    }
+
+    /// The format used for input split on `delimiter`
+    pub fn resolve(self, delimiter: &str) -> InputFormat {
+        match self {
+            InputFormat::Auto if delimiter == "," && cfg!(feature = "csv-support") => InputFormat::Csv,
+            InputFormat::Auto => InputFormat::Delimited,
+            format => format,
+        }
+    }
+}
+
+/// How JSON arrays inside objects become cells
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ArrayStyle {
+    /// One cell of compact JSON, e.g. `["a","b"]`
+    Json,
+    /// One column per element, e.g. `hobbies[0]`, flattened further like objects
+    Index,
+    /// One cell of the elements joined with `, `
+    Join,
+}
+
+impl ArrayStyle {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "json" => Some(ArrayStyle::Json),
+            "index" => Some(ArrayStyle::Index),
+            "join" => Some(ArrayStyle::Join),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ArrayStyle::Json => "json",
+            ArrayStyle::Index => "index",
+            ArrayStyle::Join => "join",
+        }
+    }
+}
+
+/// How nested JSON values are flattened into columns
+///
+/// Nested objects become dot-path columns such as `user.details.city`.
+/// Values nested deeper than `max_depth` levels stay compact JSON cells.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlattenConfig {
+    /// Levels of nesting to flatten; `None` flattens everything, `Some(0)` nothing
+    pub max_depth: Option<usize>,
+    pub arrays: ArrayStyle,
+}
+
+impl Default for FlattenConfig {
+    fn default() -> Self {
+        Self { max_depth: None, arrays: ArrayStyle::Json }
+    }
+}
+
+/// Parse table input in `format`, taking the first record as the header
+///
+/// JSON input ignores `delimiter`; its header is the union of object keys.
+pub fn parse_table(text: &str, format: InputFormat, delimiter: &str) -> Result<TableModel, FormatError> {
+    parse_table_with_config(text, format, delimiter, &FlattenConfig::default())
+}
+
+/// Parse table input in `format`, flattening JSON as `flatten` says
+pub fn parse_table_with_config(text: &str, format: InputFormat, delimiter: &str, flatten: &FlattenConfig) -> Result<TableModel, FormatError> {
+    match format.resolve(delimiter) {
+        InputFormat::Csv => Ok(TableModel::from_records(parse_csv(text, delimiter)?, true)),
+        format @ (InputFormat::Json | InputFormat::Jsonl) => {
+            Ok(TableModel::from_records(parse_json_with_config(text, format, flatten)?, true))
+        }
+        _ => Ok(TableModel::parse(text, delimiter)),
+    }
+}
+
+/// Keep the columns named by `fields`, in that order
+///
+/// A field names a column, or every column under a path prefix: `user`
+/// selects `user.name` and `user.details.city`, `tags` selects `tags[0]`.
+pub fn select_fields<S: AsRef<str>>(model: &TableModel, fields: &[S]) -> Result<TableModel, FormatError> {
+    let headers = match &model.headers {
+        Some(headers) if !fields.is_empty() => headers,
+        _ => return Ok(model.clone()),
+    };
+
+    let mut columns: Vec<usize> = Vec::new();
+    for field in fields {
+        let field = field.as_ref();
+        let matches: Vec<usize> = (0..headers.len())
+            .filter(|&i| {
+                let header = headers[i].as_str();
+                header == field
+                    || header.strip_prefix(field).is_some_and(|rest| rest.starts_with('.') || rest.starts_with('['))
+            })
+            .collect();
+        if matches.is_empty() {
+            return Err(FormatError::UnknownField(field.to_string(), headers.clone()));
+        }
+        for i in matches {
+            if !columns.contains(&i) {
+                columns.push(i);
+            }
+        }
+    }
+
+    let pick = |row: &Vec<String>| columns.iter().map(|&i| row.get(i).cloned().unwrap_or_default()).collect();
+    Ok(TableModel { headers: Some(pick(headers)), rows: model.rows.iter().map(pick).collect() })
+}
+
+/// Parse CSV text into records, with `delimiter` between fields
+pub fn parse_csv(text: &str, delimiter: &str) -> Result<Vec<Vec<String>>, FormatError> {
+    let byte = match delimiter.as_bytes() {
+        [byte] if byte.is_ascii() => *byte,
+        _ => return Err(FormatError::InvalidDelimiter("CSV".to_string(), delimiter.to_string())),
+    };
+
+    #[cfg(feature = "csv-support")]
+    {
+        crate::format::csv_input::parse_csv_records(text, byte)
+    }
+    #[cfg(not(feature = "csv-support"))]
+    {
+        let _ = (text, byte);
+        Err(FormatError::Unsupported("CSV".to_string(), "csv-support".to_string()))
+    }
+}
+
+/// Parse JSON or JSON Lines objects into records, headed by the union of their keys
+///
+/// Missing keys are empty cells and null is empty. Nested objects are
+/// flattened into dot-path columns; arrays are shown as compact JSON. A key
+/// that collides with a flattened path is [`FormatError::DuplicateColumn`].
+pub fn parse_json(text: &str, format: InputFormat) -> Result<Vec<Vec<String>>, FormatError> {
+    parse_json_with_config(text, format, &FlattenConfig::default())
+}
+
+/// Parse JSON or JSON Lines objects into records, flattened as `flatten` says
+pub fn parse_json_with_config(text: &str, format: InputFormat, flatten: &FlattenConfig) -> Result<Vec<Vec<String>>, FormatError> {
+    #[cfg(feature = "json-support")]
+    {
+        use crate::format::json_input::{flatten_object, object_records, parse_json_lines, parse_json_objects};
+
+        let objects = match format {
+            InputFormat::Jsonl => parse_json_lines(text)?,
+            _ => parse_json_objects(text)?,
+        };
+        let rows = objects
+            .iter()
+            .enumerate()
+            .map(|(i, entries)| flatten_object(entries, flatten).map_err(|column| FormatError::DuplicateColumn(column, i + 1)))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(object_records(&rows))
+    }
+    #[cfg(not(feature = "json-support"))]
+    {
+        let _ = (text, flatten);
+        Err(FormatError::Unsupported(format.as_str().to_uppercase(), "json-support".to_string()))
+    }
+}
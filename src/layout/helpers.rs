@@ -2,6 +2,8 @@
 
 #![allow(dead_code)] // Helper functions for future layout enhancements
 
+use std::borrow::Cow;
+
 /// Internal helper for calculating column widths
 pub(crate) fn calculate_column_widths(_text: &str, _cols: usize) -> Vec<usize> {
     // TODO: Implement in TASK-007
@@ -18,4 +20,17 @@ pub(crate) fn pad_text(_text: &str, _width: usize) -> String {
 pub(crate) fn split_into_rows(_text: &str, _delimiter: &str) -> Vec<Vec<String>> {
     // TODO: Implement in TASK-008
     vec![]
-}
\ No newline at end of file
+}
+
+/// Replace control characters that would break a line-based layout with `?`
+///
+/// Tabs and ANSI escape sequences are kept; only line breaks and other
+/// non-printing controls are replaced.
+pub(crate) fn printable_item(item: &str) -> Cow<'_, str> {
+    let breaks_layout = |c: char| c.is_control() && c != '\t' && c != '\x1b';
+    if item.contains(breaks_layout) {
+        Cow::Owned(item.replace(breaks_layout, "?"))
+    } else {
+        Cow::Borrowed(item)
+    }
+}
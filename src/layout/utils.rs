@@ -1,7 +1,10 @@
 //! Layout utilities - curated low-level helpers per MODULE_SPEC
 
 use crate::layout::error::LayoutError;
+use crate::layout::helpers::printable_item;
+use crate::stream::{split_records, StreamConfig};
 use crate::width::get_display_width;
+use std::borrow::Cow;
 
 /// Basic layout configuration
 pub struct LayoutConfig {
@@ -40,8 +43,16 @@ pub fn format_columns_with_delimiter(text: &str, cols: usize, config: &LayoutCon
         return Err(LayoutError::InvalidColumnCount(0));
     }
 
-    // Split input into items based on delimiter
-    let items: Vec<&str> = match delimiter {
+    format_column_items(&split_column_items(text, delimiter), cols, config)
+}
+
+/// Split input into column items: NUL records, delimited fields or lines
+///
+/// Blank lines and empty fields are skipped.
+pub fn split_column_items<'a>(text: &'a str, delimiter: Option<&str>) -> Vec<&'a str> {
+    match delimiter {
+        // NUL-delimited records (e.g. `find -print0`) may contain newlines
+        Some("\0") => split_records(text, &StreamConfig { null_data: true, ..StreamConfig::default() }),
         Some(delim) if !delim.is_empty() => {
             // First split by lines, then by custom delimiter
             let mut all_items = Vec::new();
@@ -62,25 +73,26 @@ pub fn format_columns_with_delimiter(text: &str, cols: usize, config: &LayoutCon
             // Default: split by lines only
             text.lines().filter(|line| !line.trim().is_empty()).collect()
         }
-    };
-
-    if items.is_empty() {
-        return Ok(String::new());
     }
+}
 
-    // Calculate column width
-    let total_gap_space = (cols - 1) * config.gap;
-    if config.width <= total_gap_space {
-        return Err(LayoutError::WidthTooSmall(config.width, total_gap_space));
+/// Format pre-split items into columns
+///
+/// Items are laid out as given; control characters that would break the
+/// layout (such as newlines inside NUL-delimited records) are shown as `?`.
+pub fn format_column_items<S: AsRef<str>>(items: &[S], cols: usize, config: &LayoutConfig) -> Result<String, LayoutError> {
+    if cols == 0 {
+        return Err(LayoutError::InvalidColumnCount(0));
     }
 
-    let available_width = config.width - total_gap_space;
-    let col_width = available_width / cols;
+    let items: Vec<Cow<str>> = items.iter().map(|item| printable_item(item.as_ref())).collect();
 
-    if col_width < 3 {
-        return Err(LayoutError::ColumnTooNarrow(col_width));
+    if items.is_empty() {
+        return Ok(String::new());
     }
 
+    let col_width = column_width(cols, config)?;
+
     // Calculate number of rows needed
     let rows = (items.len() + cols - 1) / cols;
     let mut result = Vec::new();
@@ -92,7 +104,7 @@ pub fn format_columns_with_delimiter(text: &str, cols: usize, config: &LayoutCon
             let item_index = row + col * rows;
 
             if item_index < items.len() {
-                let item = items[item_index];
+                let item = items[item_index].as_ref();
                 let display_width = get_display_width(item).unwrap_or(item.len());
 
                 // Add the item
@@ -127,6 +139,74 @@ pub fn format_columns_with_delimiter(text: &str, cols: usize, config: &LayoutCon
     Ok(result.join("\n"))
 }
 
+/// Narrowest column the column layout will draw
+pub const MIN_COLUMN_WIDTH: usize = 3;
+
+/// Width of each column when `cols` columns share the configured width
+pub fn column_width(cols: usize, config: &LayoutConfig) -> Result<usize, LayoutError> {
+    if cols == 0 {
+        return Err(LayoutError::InvalidColumnCount(0));
+    }
+
+    let total_gap_space = (cols - 1) * config.gap;
+    if config.width <= total_gap_space {
+        return Err(LayoutError::WidthTooSmall(config.width, total_gap_space));
+    }
+
+    let available_width = config.width - total_gap_space;
+    let col_width = available_width / cols;
+
+    if col_width < MIN_COLUMN_WIDTH {
+        return Err(LayoutError::ColumnTooNarrow(col_width));
+    }
+    Ok(col_width)
+}
+
+/// Parsed table data: an optional header row plus data rows
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TableModel {
+    pub headers: Option<Vec<String>>,
+    pub rows: Vec<Vec<String>>,
+}
+
+impl TableModel {
+    /// Parse delimited text, treating the first non-empty line as the header
+    pub fn parse(text: &str, delimiter: &str) -> Self {
+        let records = text
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| line.split(delimiter).map(|cell| cell.trim().to_string()).collect())
+            .collect();
+
+        Self::from_records(records, true)
+    }
+
+    /// Build a table from records, optionally taking the first as the header
+    pub fn from_records(mut records: Vec<Vec<String>>, has_headers: bool) -> Self {
+        let headers = if has_headers && !records.is_empty() {
+            Some(records.remove(0))
+        } else {
+            None
+        };
+
+        Self { headers, rows: records }
+    }
+
+    /// Widest row (including the header) in cells
+    pub fn column_count(&self) -> usize {
+        self.all_rows().map(|row| row.len()).max().unwrap_or(0)
+    }
+
+    /// Header (if any) followed by the data rows
+    pub fn all_rows(&self) -> impl Iterator<Item = &Vec<String>> {
+        self.headers.iter().chain(self.rows.iter())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.headers.is_none() && self.rows.is_empty()
+    }
+}
+
 /// Format text into table with delimiter and width constraints
 pub fn format_table_with_config(text: &str, delimiter: &str, width: usize) -> Result<String, LayoutError> {
     if text.trim().is_empty() {
@@ -134,32 +214,55 @@ pub fn format_table_with_config(text: &str, delimiter: &str, width: usize) -> Re
     }
 
     // Parse input into rows and columns
-    let rows: Vec<Vec<&str>> = text
-        .lines()
-        .filter(|line| !line.trim().is_empty())
-        .map(|line| line.split(delimiter).collect())
-        .collect();
+    let model = TableModel::parse(text, delimiter);
+    format_table_model(&model, width)
+}
 
-    if rows.is_empty() {
-        return Ok(String::new());
-    }
+/// Column sizing decided for a table before it is drawn
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TablePlan {
+    /// Widest cell of each column
+    pub natural_widths: Vec<usize>,
+    /// Width each column is drawn at
+    pub widths: Vec<usize>,
+    /// Natural widths plus the ` | ` separators
+    pub required_width: usize,
+    /// Factor the columns were scaled by to fit, if they didn't fit as is
+    pub scale_factor: Option<f64>,
+    /// Cells cut short with `...`
+    pub truncated: Vec<TruncatedCell>,
+}
 
-    // Determine maximum number of columns
-    let max_cols = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+/// A cell wider than its column
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TruncatedCell {
+    /// Index into [`TableModel::all_rows`], so row 0 is the header if there is one
+    pub row: usize,
+    pub column: usize,
+    /// Display width of the whole cell
+    pub width: usize,
+}
+
+/// Work out column widths for a table within a width constraint
+///
+/// Columns take the width of their widest cell. When that doesn't fit, all
+/// columns are scaled down proportionally (to no less than 3) and cells that
+/// no longer fit are truncated.
+pub fn plan_table(model: &TableModel, width: usize) -> TablePlan {
+    let max_cols = model.column_count();
     if max_cols == 0 {
-        return Ok(String::new());
+        return TablePlan::default();
     }
 
     // Calculate column widths based on content
     let mut col_widths = vec![0; max_cols];
-    for row in &rows {
+    for row in model.all_rows() {
         for (i, cell) in row.iter().enumerate() {
-            if i < max_cols {
-                let cell_width = get_display_width(cell.trim()).unwrap_or(cell.trim().len());
-                col_widths[i] = col_widths[i].max(cell_width);
-            }
+            let cell_width = get_display_width(cell.trim()).unwrap_or(cell.trim().len());
+            col_widths[i] = col_widths[i].max(cell_width);
         }
     }
+    let natural_widths = col_widths.clone();
 
     // Calculate total required width including separators
     let separator_width = 3; // " | " between columns
@@ -168,23 +271,54 @@ pub fn format_table_with_config(text: &str, delimiter: &str, width: usize) -> Re
     let required_width = total_content_width + total_separator_width;
 
     // Handle width constraints - compress columns if necessary
+    let mut scale_factor = None;
     if required_width > width && width > total_separator_width {
         let available_content_width = width - total_separator_width;
-        let scale_factor = available_content_width as f64 / total_content_width as f64;
+        let scale = available_content_width as f64 / total_content_width as f64;
 
         // Scale down column widths proportionally
         for col_width in &mut col_widths {
-            *col_width = ((*col_width as f64 * scale_factor).max(3.0)) as usize;
+            *col_width = ((*col_width as f64 * scale).max(3.0)) as usize;
         }
+        scale_factor = Some(scale);
     }
 
+    let mut truncated = Vec::new();
+    for (row_idx, row) in model.all_rows().enumerate() {
+        for (col_idx, cell) in row.iter().enumerate() {
+            let cell_width = get_display_width(cell.trim()).unwrap_or(cell.trim().len());
+            if cell_width > col_widths[col_idx] {
+                truncated.push(TruncatedCell { row: row_idx, column: col_idx, width: cell_width });
+            }
+        }
+    }
+
+    TablePlan { natural_widths, widths: col_widths, required_width, scale_factor, truncated }
+}
+
+/// Format a parsed table within a width constraint
+pub fn format_table_model(model: &TableModel, width: usize) -> Result<String, LayoutError> {
+    let rows: Vec<&Vec<String>> = model.all_rows().collect();
+
+    if rows.is_empty() {
+        return Ok(String::new());
+    }
+
+    // Determine maximum number of columns
+    let max_cols = model.column_count();
+    if max_cols == 0 {
+        return Ok(String::new());
+    }
+
+    let col_widths = plan_table(model, width).widths;
+
     // Format the table
     let mut result = Vec::new();
 
     for (row_idx, row) in rows.iter().enumerate() {
         let mut line = String::new();
 
-        for (col_idx, &cell) in row.iter().enumerate() {
+        for (col_idx, cell) in row.iter().enumerate() {
             if col_idx >= max_cols {
                 break;
             }
@@ -214,8 +348,8 @@ pub fn format_table_with_config(text: &str, delimiter: &str, width: usize) -> Re
 
         result.push(line.trim_end().to_string());
 
-        // Add header separator after first row (if it looks like a header)
-        if row_idx == 0 && rows.len() > 1 {
+        // Add header separator after the header row (when there is data below it)
+        if row_idx == 0 && model.headers.is_some() && rows.len() > 1 {
             let mut separator = String::new();
             for (col_idx, &width) in col_widths.iter().enumerate() {
                 separator.push_str(&"-".repeat(width));
@@ -230,6 +364,36 @@ pub fn format_table_with_config(text: &str, delimiter: &str, width: usize) -> Re
     Ok(result.join("\n"))
 }
 
+/// Delimiters [`guess_delimiter`] considers, in order of preference
+pub const DELIMITER_CANDIDATES: &[&str] = &["\t", ",", "|", ";"];
+
+/// The delimiter a table was most likely written with
+///
+/// A candidate must appear on every non-empty line among the first 50;
+/// candidates splitting every line into the same number of cells win, then
+/// those making more cells. Returns `None` for text that isn't delimited.
+pub fn guess_delimiter(text: &str) -> Option<&'static str> {
+    let lines: Vec<&str> = text.lines().filter(|line| !line.trim().is_empty()).take(50).collect();
+    if lines.is_empty() {
+        return None;
+    }
+
+    DELIMITER_CANDIDATES
+        .iter()
+        .filter_map(|&delimiter| {
+            let counts: Vec<usize> = lines.iter().map(|line| line.matches(delimiter).count()).collect();
+            if counts.contains(&0) {
+                return None;
+            }
+            let consistent = counts.iter().all(|&count| count == counts[0]);
+            Some((delimiter, consistent, counts[0]))
+        })
+        // max_by_key keeps the last of equals, so walk the preference order backwards
+        .rev()
+        .max_by_key(|&(_, consistent, cells)| (consistent, cells))
+        .map(|(delimiter, _, _)| delimiter)
+}
+
 /// Format text into table (convenience function)
 pub fn format_table(text: &str, delimiter: &str) -> Result<String, LayoutError> {
     format_table_with_config(text, delimiter, 80)
@@ -270,6 +434,20 @@ pub fn format_list_with_config(text: &str, config: &ListConfig) -> Result<String
 
     let lines: Vec<&str> = text.lines().filter(|line| !line.trim().is_empty()).collect();
 
+    format_list_items(&lines, config)
+}
+
+/// Format pre-split items as a list
+///
+/// Blank items are skipped; control characters inside an item (such as
+/// newlines in NUL-delimited records) are shown as `?`.
+pub fn format_list_items<S: AsRef<str>>(items: &[S], config: &ListConfig) -> Result<String, LayoutError> {
+    let lines: Vec<Cow<str>> = items
+        .iter()
+        .map(|item| printable_item(item.as_ref()))
+        .filter(|line| !line.trim().is_empty())
+        .collect();
+
     if lines.is_empty() {
         return Ok(String::new());
     }
@@ -11,26 +11,77 @@
 pub mod layout;
 pub mod width;
 pub mod stream;
+pub mod cli;
+pub mod config;
+pub mod format;
+
+/// Cargo features this build of the library was compiled with
+///
+/// Umbrella features (`full`, `minimal`) show up as the features they enable.
+pub const FEATURES: &[&str] = &[
+    #[cfg(feature = "libc")]
+    "libc",
+    #[cfg(feature = "width-boxy")]
+    "width-boxy",
+    #[cfg(feature = "width-unicode")]
+    "width-unicode",
+    #[cfg(feature = "visual")]
+    "visual",
+    #[cfg(feature = "themes")]
+    "themes",
+    #[cfg(feature = "borders")]
+    "borders",
+    #[cfg(feature = "colors")]
+    "colors",
+    #[cfg(feature = "csv-support")]
+    "csv-support",
+    #[cfg(feature = "json-support")]
+    "json-support",
+    #[cfg(feature = "markdown")]
+    "markdown",
+    #[cfg(feature = "tokens")]
+    "tokens",
+];
 
 /// Prelude with curated exports per MODULE_SPEC (RSB compliant)
 pub mod prelude {
     // Layout functionality
-    pub use crate::layout::{format_columns, format_columns_with_config, format_columns_with_delimiter, format_table, format_table_with_config, format_list, format_list_with_config, LayoutConfig, ListConfig, ListAlignment};
+    pub use crate::layout::{format_columns, format_columns_with_config, format_columns_with_delimiter, format_table, format_table_with_config, format_table_model, format_list, format_list_with_config, format_column_items, format_list_items, LayoutConfig, ListConfig, ListAlignment, TableModel};
+
+    // Structured table input
+    pub use crate::format::{parse_table, parse_table_with_config, parse_csv, parse_json, parse_json_with_config, select_fields, InputFormat, FlattenConfig, ArrayStyle};
 
     // Width calculation functionality
-    pub use crate::width::{get_display_width, get_terminal_width, validate_width, check_terminal_resize};
+    pub use crate::width::{get_display_width, get_terminal_width, validate_width, check_terminal_resize, strip_ansi, WIDTH_STRATEGY};
 
     // Stream processing functionality
     pub use crate::stream::{
         read_stdin, write_stdout, stdin_to_stream, stream_to_stdout,
         pipe_transform, pipe_lines, create_pipeline, Pipeline,
-        StreamConfig, LineEnding
+        StagedPipeline, Lines, Records, stages,
+        read_stdin_with_config, read_stdin_decoded, write_stdout_with_config, write_file_atomic,
+        ColorPolicy,
+        decode_input, DecodedInput, InputEncoding, check_binary, escape_control_chars, prepare_text,
+        OutputWriter, is_broken_pipe, BROKEN_PIPE_EXIT_CODE,
+        StreamConfig, LineEnding, normalize_line_endings, apply_line_ending, split_records,
+        follow_input, follow_reader, FollowConfig, FollowSource,
+        watch_command, WatchConfig, parse_interval,
+        run_command, exec_command, exec_command_bytes, filter_through_command, filter_through_commands,
+        shell_join, CommandOutput
     };
 
+    // Command line interface (shared by the binary and embedding tools)
+    pub use crate::FEATURES;
+    pub use crate::cli::{CliConfig, CliMode, parse_args, apply_setting, run_cli, completion_script, man_page, Shell};
+    pub use crate::config::{resolve_config, resolve_config_from, resolve_config_with, ConfigFile, ResolvedConfig, ValueSource};
+
     // Error types for comprehensive error handling
     pub use crate::width::error::WidthError;
     pub use crate::layout::error::LayoutError;
     pub use crate::stream::StreamError;
+    pub use crate::cli::error::{CliError, ErrorFormat, ErrorKind};
+    pub use crate::config::error::ConfigError;
+    pub use crate::format::error::FormatError;
 
     // Module-owned macros
     pub use crate::layout_config;
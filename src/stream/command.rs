@@ -0,0 +1,187 @@
+//! External command execution
+//!
+//! Runs commands through the internal `spawn_pipe_command` helper so watch
+//! and exec modes can lay out another program's output, and so pipelines can
+//! chain external filters before and after layout.
+
+use super::error::{StreamError, StreamResult};
+use super::helpers::spawn_pipe_command;
+use std::io::{self, Read, Write};
+use std::thread;
+
+/// Captured result of running an external command
+#[derive(Debug, Clone)]
+pub struct CommandOutput {
+    /// Everything the command wrote to stdout
+    pub stdout: String,
+    /// Everything the command wrote to stderr
+    pub stderr: String,
+    /// Exit code, or 128 + signal number if the command was killed
+    pub exit_code: i32,
+}
+
+impl CommandOutput {
+    pub fn success(&self) -> bool {
+        self.exit_code == 0
+    }
+}
+
+/// Join argv into a single shell command line, quoting each word
+///
+/// Words are single-quoted so `sh -c` sees exactly the original arguments,
+/// without any globbing or variable expansion.
+pub fn shell_join(argv: &[String]) -> String {
+    argv.iter()
+        .map(|arg| {
+            let safe = !arg.is_empty()
+                && arg.chars().all(|c| c.is_ascii_alphanumeric() || "-_./=:,+@%".contains(c));
+            if safe {
+                arg.clone()
+            } else {
+                format!("'{}'", arg.replace('\'', "'\\''"))
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Run a shell command to completion, capturing stdout and stderr
+pub fn run_command(command: &str) -> StreamResult<CommandOutput> {
+    let mut child = spawn_pipe_command(command)?;
+
+    // The command gets no input from us
+    drop(child.stdin.take());
+
+    let output = child
+        .wait_with_output()
+        .map_err(|e| StreamError::IoError(format!("Failed to run command '{}': {}", command, e)))?;
+
+    Ok(CommandOutput {
+        stdout: String::from_utf8_lossy(&output.stdout).to_string(),
+        stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+        exit_code: exit_code_of(&output.status),
+    })
+}
+
+/// Run argv directly, capturing stdout while forwarding stderr as it arrives
+///
+/// Arguments are quoted with [`shell_join`] so no shell quoting is needed by
+/// the caller. The returned `stderr` is always empty since it was forwarded.
+pub fn exec_command(argv: &[String]) -> StreamResult<CommandOutput> {
+    let (stdout, exit_code) = exec_command_bytes(argv)?;
+    Ok(CommandOutput {
+        stdout: String::from_utf8_lossy(&stdout).to_string(),
+        stderr: String::new(),
+        exit_code,
+    })
+}
+
+/// Like [`exec_command`], returning stdout as raw bytes with the exit code
+///
+/// For callers that decode output themselves (e.g. with `--encoding`).
+pub fn exec_command_bytes(argv: &[String]) -> StreamResult<(Vec<u8>, i32)> {
+    if argv.is_empty() {
+        return Err(StreamError::IoError("No command given to execute".to_string()));
+    }
+
+    let command = shell_join(argv);
+    let mut child = spawn_pipe_command(&command)?;
+
+    // The command gets no input from us
+    drop(child.stdin.take());
+
+    let forwarder = child.stderr.take().map(|mut child_stderr| {
+        thread::spawn(move || {
+            let _ = io::copy(&mut child_stderr, &mut io::stderr());
+        })
+    });
+
+    let mut stdout = Vec::new();
+    if let Some(mut child_stdout) = child.stdout.take() {
+        child_stdout.read_to_end(&mut stdout).map_err(StreamError::from)?;
+    }
+
+    let status = child
+        .wait()
+        .map_err(|e| StreamError::IoError(format!("Failed to run command '{}': {}", command, e)))?;
+
+    if let Some(handle) = forwarder {
+        let _ = handle.join();
+    }
+
+    Ok((stdout, exit_code_of(&status)))
+}
+
+/// Pipe `input` through a shell command and return what it writes to stdout
+///
+/// Used for pre/post pipeline stages (e.g. jynx before layout, boxy after).
+/// The command's stderr is forwarded; a non-zero exit is reported as
+/// [`StreamError::CommandFailed`].
+pub fn filter_through_command(command: &str, input: &str) -> StreamResult<String> {
+    let mut child = spawn_pipe_command(command)?;
+
+    // Feed input from a separate thread so a chatty filter can't deadlock us.
+    // Write errors are ignored: filters like `head` may stop reading early.
+    let feeder = child.stdin.take().map(|mut child_stdin| {
+        let input = input.to_string();
+        thread::spawn(move || {
+            let _ = child_stdin.write_all(input.as_bytes());
+        })
+    });
+
+    let forwarder = child.stderr.take().map(|mut child_stderr| {
+        thread::spawn(move || {
+            let _ = io::copy(&mut child_stderr, &mut io::stderr());
+        })
+    });
+
+    let mut stdout = Vec::new();
+    if let Some(mut child_stdout) = child.stdout.take() {
+        child_stdout.read_to_end(&mut stdout).map_err(StreamError::from)?;
+    }
+
+    let status = child
+        .wait()
+        .map_err(|e| StreamError::IoError(format!("Failed to run command '{}': {}", command, e)))?;
+
+    for handle in [feeder, forwarder].into_iter().flatten() {
+        let _ = handle.join();
+    }
+
+    let exit_code = exit_code_of(&status);
+    if exit_code != 0 {
+        return Err(StreamError::CommandFailed(command.to_string(), exit_code));
+    }
+
+    String::from_utf8(stdout).map_err(StreamError::from)
+}
+
+/// Run content through a chain of filter commands, in order
+pub fn filter_through_commands(commands: &[String], content: String) -> StreamResult<String> {
+    commands
+        .iter()
+        .try_fold(content, |content, command| filter_through_command(command, &content))
+}
+
+/// Exit code of a finished child, using the shell convention for signals
+pub(crate) fn exit_code_of(status: &std::process::ExitStatus) -> i32 {
+    use std::os::unix::process::ExitStatusExt;
+
+    match (status.code(), status.signal()) {
+        (Some(code), _) => code,
+        (None, Some(signal)) => 128 + signal,
+        (None, None) => 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shell_join_quotes_unsafe_words() {
+        let argv = vec!["ls".to_string(), "-la".to_string(), "my file".to_string(), "it's".to_string()];
+        assert_eq!(shell_join(&argv), "ls -la 'my file' 'it'\\''s'");
+        assert_eq!(shell_join(&["".to_string()]), "''");
+    }
+}
@@ -20,6 +20,12 @@ pub enum StreamError {
     BufferOverflow(usize),
     /// Invalid UTF-8 in stream
     InvalidUtf8(String),
+    /// Input looks like binary data rather than text
+    BinaryInput(String),
+    /// External command exited unsuccessfully (command, exit code)
+    CommandFailed(String, i32),
+    /// Typed pipeline stage failed
+    StageFailed(String),
     /// Generic I/O error
     IoError(String),
 }
@@ -33,6 +39,9 @@ impl fmt::Display for StreamError {
             StreamError::UnexpectedEof => write!(f, "Unexpected end of file"),
             StreamError::BufferOverflow(size) => write!(f, "Buffer overflow at {} bytes", size),
             StreamError::InvalidUtf8(msg) => write!(f, "Invalid UTF-8 encoding: {}", msg),
+            StreamError::BinaryInput(msg) => write!(f, "Input appears to be binary: {}", msg),
+            StreamError::CommandFailed(cmd, code) => write!(f, "Command '{}' failed with exit code {}", cmd, code),
+            StreamError::StageFailed(msg) => write!(f, "Pipeline stage failed: {}", msg),
             StreamError::IoError(msg) => write!(f, "I/O error: {}", msg),
         }
     }
@@ -57,6 +66,13 @@ impl From<std::io::Error> for StreamError {
     }
 }
 
+/// Convert layout errors raised inside pipeline stages to StreamError
+impl From<crate::layout::error::LayoutError> for StreamError {
+    fn from(err: crate::layout::error::LayoutError) -> Self {
+        StreamError::StageFailed(err.to_string())
+    }
+}
+
 /// Convert std::string::FromUtf8Error to StreamError
 impl From<std::string::FromUtf8Error> for StreamError {
     fn from(err: std::string::FromUtf8Error) -> Self {
@@ -0,0 +1,259 @@
+//! Follow mode - re-lay out input as it grows
+//!
+//! Keeps reading from a file or stdin (think `tail -f` plus `column`) and hands
+//! the accumulated input to a render callback whenever new lines arrive or the
+//! terminal is resized. On a TTY the layout is redrawn in place; otherwise
+//! each update is written as a complete new frame, since a grown layout can
+//! reflow lines that were already written (columns fill top to bottom, table
+//! widths change).
+
+use super::error::{StreamError, StreamResult};
+use super::helpers::{is_tty_output, LiveRegion, CHUNK_SIZE};
+use super::output::OutputWriter;
+use super::utils::{check_binary, decode_input, prepare_text, stdin_has_data, StreamConfig};
+use crate::width::check_terminal_resize;
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::PathBuf;
+use std::thread;
+use std::time::Duration;
+
+/// Input source for follow mode
+#[derive(Debug, Clone)]
+pub enum FollowSource {
+    /// Follow stdin until EOF
+    Stdin,
+    /// Follow a file, picking up appended data and truncation
+    File(PathBuf),
+}
+
+/// Configuration for follow mode
+#[derive(Debug, Clone)]
+pub struct FollowConfig {
+    /// Where to read input from
+    pub source: FollowSource,
+    /// How often to poll for new input and terminal resizes
+    pub poll_interval: Duration,
+    /// Redraw in place with cursor control (defaults to stdout being a TTY)
+    pub in_place: bool,
+    /// Stream settings; `max_buffer_size` bounds the retained input, and the
+    /// line ending settings apply to input and appended frames
+    pub stream: StreamConfig,
+}
+
+impl Default for FollowConfig {
+    fn default() -> Self {
+        Self {
+            source: FollowSource::Stdin,
+            poll_interval: Duration::from_millis(250),
+            in_place: is_tty_output(),
+            stream: StreamConfig::default(),
+        }
+    }
+}
+
+/// Follow input and re-render the layout each time it grows
+///
+/// `render` receives all complete lines read so far and returns the laid out
+/// text. Following stdin ends at EOF; following a file runs until the process
+/// is interrupted.
+pub fn follow_input<F, E>(config: &FollowConfig, render: F) -> Result<(), E>
+where
+    F: FnMut(&str) -> Result<String, E>,
+    E: From<StreamError>,
+{
+    let reader = FollowReader::open(&config.source)?;
+    follow_with(config, reader, OutputWriter::stdout(&config.stream), render)
+}
+
+/// Follow `input` instead of the configured source, writing frames to `out`
+///
+/// Every read that returns data counts as new input; a read of zero bytes
+/// ends following, like EOF on stdin.
+pub fn follow_reader<R, W, F, E>(config: &FollowConfig, input: R, out: W, render: F) -> Result<(), E>
+where
+    R: Read,
+    W: Write,
+    F: FnMut(&str) -> Result<String, E>,
+    E: From<StreamError>,
+{
+    follow_with(config, FollowReader::Reader(Box::new(input)), OutputWriter::new(out, &config.stream), render)
+}
+
+fn follow_with<W, F, E>(config: &FollowConfig, mut reader: FollowReader<'_>, mut out: OutputWriter<W>, mut render: F) -> Result<(), E>
+where
+    W: Write,
+    F: FnMut(&str) -> Result<String, E>,
+    E: From<StreamError>,
+{
+    let mut region = LiveRegion::new();
+    let mut buffer: Vec<u8> = Vec::new();
+    let mut last_frame: Option<String> = None;
+    let terminator = if config.stream.null_data { b'\0' } else { b'\n' };
+
+    // Prime resize detection so the first poll doesn't report a change
+    let _ = check_terminal_resize();
+
+    loop {
+        let status = reader.poll(&mut buffer)?;
+        trim_to_limit(&mut buffer, config.stream.max_buffer_size, terminator);
+
+        let resized = config.in_place && check_terminal_resize().is_some();
+
+        if status.grew || status.truncated || resized || last_frame.is_none() {
+            let complete = complete_lines(&buffer, status.eof, terminator);
+            check_binary(complete, &config.stream)?;
+            let content = decode_input(complete.to_vec(), config.stream.encoding)?.text;
+            let rendered = render(&prepare_text(&content, &config.stream))?;
+
+            if config.in_place {
+                region.redraw(&mut out, &rendered, resized)?;
+            } else if !rendered.is_empty() && last_frame.as_ref() != Some(&rendered) {
+                // A blank line separates frames, as in watch mode
+                out.write_str(&format!("{}\n\n", rendered))?;
+                out.flush()?;
+            }
+            last_frame = Some(rendered);
+        }
+
+        if status.eof {
+            return Ok(());
+        }
+
+        thread::sleep(config.poll_interval);
+    }
+}
+
+/// Outcome of a single poll of the follow source
+struct PollStatus {
+    grew: bool,
+    truncated: bool,
+    eof: bool,
+}
+
+/// Incremental reader over the follow source
+enum FollowReader<'a> {
+    Stdin,
+    File { file: File, position: u64 },
+    /// Any reader; each read is one batch of new input
+    Reader(Box<dyn Read + 'a>),
+}
+
+impl FollowReader<'_> {
+    fn open(source: &FollowSource) -> StreamResult<Self> {
+        match source {
+            FollowSource::Stdin => Ok(FollowReader::Stdin),
+            FollowSource::File(path) => {
+                let file = File::open(path).map_err(|e| {
+                    StreamError::IoError(format!("Failed to open '{}': {}", path.display(), e))
+                })?;
+                Ok(FollowReader::File { file, position: 0 })
+            }
+        }
+    }
+
+    /// Append whatever is currently available to `buffer`
+    ///
+    /// Stdin and files never block; a `Reader` waits for its next read.
+    fn poll(&mut self, buffer: &mut Vec<u8>) -> StreamResult<PollStatus> {
+        let mut chunk = [0u8; CHUNK_SIZE];
+
+        match self {
+            FollowReader::Stdin => {
+                let mut grew = false;
+                while stdin_has_data() {
+                    let read = io::stdin().lock().read(&mut chunk).map_err(StreamError::from)?;
+                    if read == 0 {
+                        return Ok(PollStatus { grew, truncated: false, eof: true });
+                    }
+                    buffer.extend_from_slice(&chunk[..read]);
+                    grew = true;
+                }
+                Ok(PollStatus { grew, truncated: false, eof: false })
+            }
+            FollowReader::File { file, position } => {
+                let mut truncated = false;
+                let length = file.metadata().map_err(StreamError::from)?.len();
+                if length < *position {
+                    // File was truncated or rotated in place - start over
+                    file.seek(SeekFrom::Start(0)).map_err(StreamError::from)?;
+                    *position = 0;
+                    buffer.clear();
+                    truncated = true;
+                }
+
+                let mut grew = false;
+                loop {
+                    let read = file.read(&mut chunk).map_err(StreamError::from)?;
+                    if read == 0 {
+                        break;
+                    }
+                    buffer.extend_from_slice(&chunk[..read]);
+                    *position += read as u64;
+                    grew = true;
+                }
+                Ok(PollStatus { grew, truncated, eof: false })
+            }
+            FollowReader::Reader(input) => {
+                let read = input.read(&mut chunk).map_err(StreamError::from)?;
+                buffer.extend_from_slice(&chunk[..read]);
+                Ok(PollStatus { grew: read > 0, truncated: false, eof: read == 0 })
+            }
+        }
+    }
+}
+
+/// Slice of `buffer` up to the last terminator; partial records wait for more input
+fn complete_lines(buffer: &[u8], eof: bool, terminator: u8) -> &[u8] {
+    if eof {
+        return buffer;
+    }
+    match buffer.iter().rposition(|&b| b == terminator) {
+        Some(pos) => &buffer[..=pos],
+        None => &[],
+    }
+}
+
+/// Drop the oldest records once the retained input exceeds `max_size` bytes
+///
+/// The cut is made after a `terminator`, so no record is kept half.
+fn trim_to_limit(buffer: &mut Vec<u8>, max_size: usize, terminator: u8) {
+    if buffer.len() <= max_size {
+        return;
+    }
+    let excess = buffer.len() - max_size;
+    let cut = match buffer[excess..].iter().position(|&b| b == terminator) {
+        Some(pos) => excess + pos + 1,
+        None => excess,
+    };
+    buffer.drain(..cut);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_complete_lines_holds_back_partial_line() {
+        assert_eq!(complete_lines(b"a\nb\npart", false, b'\n'), b"a\nb\n");
+        assert_eq!(complete_lines(b"a\nb\npart", true, b'\n'), b"a\nb\npart");
+        assert_eq!(complete_lines(b"partial", false, b'\n'), b"");
+        assert_eq!(complete_lines(b"a b\0c\nd", false, b'\0'), b"a b\0");
+    }
+
+    #[test]
+    fn test_trim_to_limit_drops_whole_lines() {
+        let mut buffer = b"first\nsecond\nthird\n".to_vec();
+        trim_to_limit(&mut buffer, 10, b'\n');
+        assert_eq!(buffer, b"third\n");
+
+        let mut small = b"ok\n".to_vec();
+        trim_to_limit(&mut small, 10, b'\n');
+        assert_eq!(small, b"ok\n");
+
+        // NUL records may hold newlines; only NULs end them
+        let mut records = b"one\0two\nlines\0three\0".to_vec();
+        trim_to_limit(&mut records, 12, b'\0');
+        assert_eq!(records, b"three\0");
+    }
+}
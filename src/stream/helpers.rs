@@ -6,7 +6,8 @@
 #![allow(dead_code)] // Helper functions for future stream processing features
 
 use super::error::{StreamError, StreamResult};
-use std::io::{self, BufRead};
+use super::output::OutputWriter;
+use std::io::{self, BufRead, Write};
 use std::process::{Command, Stdio};
 
 /// Buffer size for chunk reading operations
@@ -25,6 +26,16 @@ pub(crate) fn is_piped_input() -> bool {
     }
 }
 
+/// Internal helper to detect if stdout is attached to a terminal
+pub(crate) fn is_tty_output() -> bool {
+    use std::os::unix::io::AsRawFd;
+
+    let stdout_fd = io::stdout().as_raw_fd();
+    unsafe {
+        rsb::deps::libc::isatty(stdout_fd) == 1
+    }
+}
+
 /// Internal helper to detect terminal width from environment
 pub(crate) fn detect_terminal_width() -> Option<usize> {
     // Try COLUMNS environment variable first
@@ -83,9 +94,27 @@ pub(crate) fn validate_utf8_content(content: &[u8]) -> StreamResult<String> {
         Ok(s) => Ok(s),
         Err(_) => {
             // Try to recover by replacing invalid sequences
-            Ok(String::from_utf8_lossy(content).to_string())
+            Ok(decode_utf8_lossy(content).0)
+        }
+    }
+}
+
+/// Internal helper to decode UTF-8, replacing invalid sequences with U+FFFD
+///
+/// Returns the text along with the number of invalid bytes that were replaced.
+pub(crate) fn decode_utf8_lossy(content: &[u8]) -> (String, usize) {
+    let mut text = String::with_capacity(content.len());
+    let mut replaced = 0;
+
+    for chunk in content.utf8_chunks() {
+        text.push_str(chunk.valid());
+        if !chunk.invalid().is_empty() {
+            text.push(char::REPLACEMENT_CHARACTER);
+            replaced += chunk.invalid().len();
         }
     }
+
+    (text, replaced)
 }
 
 /// Internal helper to handle SIGPIPE setup
@@ -102,6 +131,47 @@ pub(crate) fn setup_sigpipe_handler() {
     }
 }
 
+/// Internal helper that redraws a block of terminal output in place
+///
+/// Remembers how many lines the previous frame occupied so the next frame can
+/// move the cursor back up over it and clear to the end of the screen.
+pub(crate) struct LiveRegion {
+    drawn_lines: usize,
+}
+
+impl LiveRegion {
+    pub(crate) fn new() -> Self {
+        Self { drawn_lines: 0 }
+    }
+
+    /// Replace the previously drawn frame with `frame`
+    ///
+    /// `full_clear` wipes the whole screen first, which is needed after a
+    /// resize since old lines may have re-wrapped.
+    pub(crate) fn redraw<W: Write>(&mut self, out: &mut OutputWriter<W>, frame: &str, full_clear: bool) -> StreamResult<()> {
+        let mut buffer = String::new();
+
+        if full_clear {
+            buffer.push_str("\x1b[2J\x1b[H");
+        } else if self.drawn_lines > 0 {
+            // Cursor up over the previous frame, then clear to end of screen
+            buffer.push_str(&format!("\r\x1b[{}A", self.drawn_lines));
+        }
+        buffer.push_str("\x1b[J");
+
+        if !frame.is_empty() {
+            buffer.push_str(frame);
+            buffer.push('\n');
+        }
+
+        out.write_raw(&buffer)?;
+        out.flush()?;
+
+        self.drawn_lines = if frame.is_empty() { 0 } else { frame.lines().count() };
+        Ok(())
+    }
+}
+
 /// Internal helper to create a child process for piping
 pub(crate) fn spawn_pipe_command(command: &str) -> StreamResult<std::process::Child> {
     Command::new("sh")
@@ -122,15 +192,45 @@ pub(crate) fn estimate_content_size(content: &str) -> usize {
 
 /// Internal helper to check if content might be binary
 pub(crate) fn is_likely_binary(content: &[u8]) -> bool {
-    // Simple heuristic: if more than 30% of the first 8KB contains null bytes or high-bit chars
+    binary_evidence(content).is_some()
+}
+
+/// Internal helper describing why content looks binary, if it does
+///
+/// Sniffs the first 8KB: any NUL byte is conclusive, otherwise the sample is
+/// binary when more than 30% of it is control bytes or invalid UTF-8. Valid
+/// multi-byte UTF-8 never counts against the content.
+pub(crate) fn binary_evidence(content: &[u8]) -> Option<String> {
     let sample_size = std::cmp::min(content.len(), 8192);
+    if sample_size == 0 {
+        return None;
+    }
     let sample = &content[..sample_size];
 
-    let suspicious_bytes = sample.iter()
-        .filter(|&&b| b == 0 || b > 127)
+    if let Some(offset) = sample.iter().position(|&b| b == 0) {
+        return Some(format!("NUL byte at offset {}", offset));
+    }
+
+    let control_bytes = sample.iter()
+        .filter(|&&b| (b < 0x20 && !b"\t\n\r\x0c\x08\x1b".contains(&b)) || b == 0x7f)
         .count();
 
-    (suspicious_bytes as f64 / sample_size as f64) > 0.30
+    let mut invalid_bytes = 0;
+    let mut chunks = sample.utf8_chunks().peekable();
+    while let Some(chunk) = chunks.next() {
+        // A multi-byte character cut off by the sample boundary is not invalid
+        let cut_off = chunks.peek().is_none() && sample_size < content.len();
+        if !cut_off {
+            invalid_bytes += chunk.invalid().len();
+        }
+    }
+
+    let suspicious_bytes = control_bytes + invalid_bytes;
+    if (suspicious_bytes as f64 / sample_size as f64) > 0.30 {
+        Some(format!("{} of the first {} bytes are control bytes or invalid UTF-8", suspicious_bytes, sample_size))
+    } else {
+        None
+    }
 }
 
 /// Internal helper to split content preserving line endings
@@ -200,6 +300,14 @@ mod tests {
         assert!(is_likely_binary(binary_content));
     }
 
+    #[test]
+    fn test_binary_evidence_ignores_valid_utf8() {
+        assert!(binary_evidence("日本語のテキスト\nüñíçødé\n".as_bytes()).is_none());
+        assert!(binary_evidence(b"").is_none());
+        assert_eq!(binary_evidence(b"ELF\x02\x01\x00rest").as_deref(), Some("NUL byte at offset 5"));
+        assert!(binary_evidence(&[0xff, 0xfe, 0x01, 0x02, b'a']).is_some());
+    }
+
     #[test]
     fn test_split_preserving_endings() {
         let content = "line1\nline2\nline3";
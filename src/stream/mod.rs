@@ -7,8 +7,23 @@ mod utils;
 mod helpers;
 mod error;
 
+// Built-in stages for typed pipelines
+pub mod stages;
+
+// Buffered output writer shared by all commands
+mod output;
+
+// External commands and streaming modes
+mod command;
+mod follow;
+mod watch;
+
 pub use utils::*;
 pub use error::*;
+pub use output::*;
+pub use command::*;
+pub use follow::*;
+pub use watch::*;
 
 // Internal helpers are not re-exported
 // use helpers::*; // Currently unused
\ No newline at end of file
@@ -0,0 +1,133 @@
+//! Buffered output writer
+//!
+//! Every command renders through [`OutputWriter`] so broken pipes and write
+//! failures behave the same everywhere: a reader that goes away (`rolo table
+//! big.tsv | head`) surfaces as [`StreamError::PipeBroken`], which callers
+//! turn into a quiet exit with [`BROKEN_PIPE_EXIT_CODE`]; anything else is a
+//! [`StreamError::StdoutWrite`].
+
+use super::error::{StreamError, StreamResult};
+use super::helpers::{is_tty_output, setup_sigpipe_handler};
+use super::utils::{apply_line_ending, LineEnding, StreamConfig};
+use crate::width::strip_ansi;
+use std::io::{self, BufWriter, Write};
+use std::sync::Once;
+
+/// Conventional exit status when the output pipe is closed (128 + SIGPIPE)
+pub const BROKEN_PIPE_EXIT_CODE: i32 = 141;
+
+static SIGPIPE_SETUP: Once = Once::new();
+
+/// Buffered writer that converts line endings, applies the color policy and
+/// classifies write errors
+pub struct OutputWriter<W: Write> {
+    inner: BufWriter<W>,
+    line_ending: LineEnding,
+    strip_colors: bool,
+}
+
+impl OutputWriter<io::Stdout> {
+    /// Writer over stdout using the config's line ending
+    ///
+    /// When `handle_sigpipe` is set, SIGPIPE is ignored so a closed pipe shows
+    /// up as a write error instead of killing the process mid-write.
+    pub fn stdout(config: &StreamConfig) -> Self {
+        if config.handle_sigpipe {
+            SIGPIPE_SETUP.call_once(setup_sigpipe_handler);
+        }
+        Self {
+            strip_colors: config.color.strips_for(is_tty_output()),
+            ..Self::new(io::stdout(), config)
+        }
+    }
+}
+
+impl<W: Write> OutputWriter<W> {
+    /// Writer over any target, which the color policy treats as a non-TTY
+    pub fn new(writer: W, config: &StreamConfig) -> Self {
+        Self {
+            inner: BufWriter::new(writer),
+            line_ending: config.line_ending,
+            strip_colors: config.color.strips_for(false),
+        }
+    }
+
+    /// Write text, converting `\n` line breaks and stripping colors per the config
+    pub fn write_str(&mut self, content: &str) -> StreamResult<()> {
+        let converted = if self.strip_colors {
+            apply_line_ending(&strip_ansi(content), self.line_ending)
+        } else {
+            apply_line_ending(content, self.line_ending)
+        };
+        self.write_raw(&converted)
+    }
+
+    /// Write text followed by the configured line ending
+    pub fn write_line(&mut self, line: &str) -> StreamResult<()> {
+        self.write_str(line)?;
+        self.write_raw(self.line_ending.as_str())
+    }
+
+    /// Write text exactly as given (e.g. terminal control sequences)
+    pub fn write_raw(&mut self, content: &str) -> StreamResult<()> {
+        self.inner.write_all(content.as_bytes()).map_err(write_error)
+    }
+
+    /// Flush buffered output; streaming modes call this after every frame
+    pub fn flush(&mut self) -> StreamResult<()> {
+        self.inner.flush().map_err(write_error)
+    }
+
+    /// Flush and return the underlying writer
+    pub fn into_inner(self) -> StreamResult<W> {
+        self.inner.into_inner().map_err(|e| write_error(e.into_error()))
+    }
+}
+
+/// Whether an error means the reader of our output went away
+pub fn is_broken_pipe(error: &StreamError) -> bool {
+    matches!(error, StreamError::PipeBroken(_))
+}
+
+/// Classify a write failure: broken pipes are expected, the rest are not
+fn write_error(err: io::Error) -> StreamError {
+    if err.kind() == io::ErrorKind::BrokenPipe {
+        StreamError::PipeBroken(err.to_string())
+    } else {
+        StreamError::StdoutWrite(err.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Writer whose reader has gone away
+    struct ClosedPipe;
+
+    impl Write for ClosedPipe {
+        fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+            Err(io::Error::from(io::ErrorKind::BrokenPipe))
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Err(io::Error::from(io::ErrorKind::BrokenPipe))
+        }
+    }
+
+    #[test]
+    fn test_broken_pipe_is_reported_on_flush() {
+        let mut out = OutputWriter::new(ClosedPipe, &StreamConfig::default());
+        out.write_line("buffered").unwrap();
+
+        let err = out.flush().unwrap_err();
+        assert!(is_broken_pipe(&err));
+    }
+
+    #[test]
+    fn test_write_error_classification() {
+        let other = write_error(io::Error::from(io::ErrorKind::PermissionDenied));
+        assert!(matches!(other, StreamError::StdoutWrite(_)));
+        assert!(!is_broken_pipe(&other));
+    }
+}
@@ -0,0 +1,104 @@
+//! Built-in stages for typed pipelines
+//!
+//! Each constructor returns a closure usable with [`Pipeline::stage`] and
+//! [`StagedPipeline::stage`], covering the lines → records → table model →
+//! rendered lines flow that rolo itself uses.
+//!
+//! [`Pipeline::stage`]: super::Pipeline::stage
+//! [`StagedPipeline::stage`]: super::StagedPipeline::stage
+
+use super::error::StreamResult;
+use super::utils::{Lines, Records};
+use crate::layout::{format_columns_with_config, format_list_with_config, format_table_model};
+use crate::layout::{LayoutConfig, ListConfig, TableModel};
+use std::cmp::Ordering;
+
+/// Sort direction for [`sort_rows`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SortOrder {
+    Ascending,
+    Descending,
+}
+
+/// Split non-empty lines into trimmed fields
+pub fn parse_delimited(delimiter: &str) -> impl FnMut(Lines) -> StreamResult<Records> {
+    let delimiter = delimiter.to_string();
+    move |lines| {
+        Ok(lines
+            .iter()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| line.split(delimiter.as_str()).map(|cell| cell.trim().to_string()).collect())
+            .collect())
+    }
+}
+
+/// Turn records into a table model, optionally taking the first as the header
+pub fn to_table(has_headers: bool) -> impl FnMut(Records) -> StreamResult<TableModel> {
+    move |records| Ok(TableModel::from_records(records, has_headers))
+}
+
+/// Keep only lines matching the predicate
+pub fn filter_lines<P>(mut predicate: P) -> impl FnMut(Lines) -> StreamResult<Lines>
+where
+    P: FnMut(&str) -> bool,
+{
+    move |lines| Ok(lines.into_iter().filter(|line| predicate(line)).collect())
+}
+
+/// Keep only data rows matching the predicate; the header is always kept
+pub fn filter_rows<P>(mut predicate: P) -> impl FnMut(TableModel) -> StreamResult<TableModel>
+where
+    P: FnMut(&[String]) -> bool,
+{
+    move |mut model| {
+        model.rows.retain(|row| predicate(row));
+        Ok(model)
+    }
+}
+
+/// Sort data rows by a zero-based column, numerically when both cells are numbers
+///
+/// Rows missing the column sort first. The sort is stable and never moves the header.
+pub fn sort_rows(column: usize, order: SortOrder) -> impl FnMut(TableModel) -> StreamResult<TableModel> {
+    move |mut model| {
+        model.rows.sort_by(|a, b| {
+            let ordering = compare_cells(a.get(column), b.get(column));
+            match order {
+                SortOrder::Ascending => ordering,
+                SortOrder::Descending => ordering.reverse(),
+            }
+        });
+        Ok(model)
+    }
+}
+
+/// Render a table model into lines within `width`
+pub fn layout_table(width: usize) -> impl FnMut(TableModel) -> StreamResult<Lines> {
+    move |model| Ok(split_rendered(&format_table_model(&model, width)?))
+}
+
+/// Render lines as `cols` columns
+pub fn layout_columns(cols: usize, config: LayoutConfig) -> impl FnMut(Lines) -> StreamResult<Lines> {
+    move |lines| Ok(split_rendered(&format_columns_with_config(&lines.join("\n"), cols, &config)?))
+}
+
+/// Render lines as a list
+pub fn layout_list(config: ListConfig) -> impl FnMut(Lines) -> StreamResult<Lines> {
+    move |lines| Ok(split_rendered(&format_list_with_config(&lines.join("\n"), &config)?))
+}
+
+fn compare_cells(a: Option<&String>, b: Option<&String>) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => match (a.parse::<f64>(), b.parse::<f64>()) {
+            (Ok(x), Ok(y)) => x.partial_cmp(&y).unwrap_or(Ordering::Equal),
+            _ => a.cmp(b),
+        },
+        (None, Some(_)) => Ordering::Less,
+        (Some(_), None) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+fn split_rendered(rendered: &str) -> Lines {
+    rendered.lines().map(|line| line.to_string()).collect()
+}
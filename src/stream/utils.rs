@@ -3,9 +3,14 @@
 //! Public API for handling stdin/stdout, pipe operations, and buffered reading.
 //! Leverages RSB's Stream type for robust text processing.
 
+use super::command::filter_through_commands;
 use super::error::{StreamError, StreamResult};
+use super::helpers::{binary_evidence, decode_utf8_lossy};
+use super::output::{is_broken_pipe, OutputWriter};
 use rsb::prelude::Stream;
+use std::fs::{self, OpenOptions};
 use std::io::{self, Read, Write, BufReader};
+use std::path::Path;
 use std::time::Duration;
 
 /// Configuration for stream processing
@@ -17,6 +22,16 @@ pub struct StreamConfig {
     pub handle_sigpipe: bool,
     /// Line ending style for output
     pub line_ending: LineEnding,
+    /// Convert CRLF/CR input line endings to `\n` on read
+    pub normalize_line_endings: bool,
+    /// Input records are NUL-delimited (`find -print0`) instead of lines
+    pub null_data: bool,
+    /// How input bytes are decoded into text
+    pub encoding: InputEncoding,
+    /// Accept input that looks binary, escaping its control bytes
+    pub allow_binary: bool,
+    /// Whether ANSI colors are kept in output
+    pub color: ColorPolicy,
 }
 
 impl Default for StreamConfig {
@@ -25,16 +40,99 @@ impl Default for StreamConfig {
             max_buffer_size: 10 * 1024 * 1024, // 10MB default
             handle_sigpipe: true,
             line_ending: LineEnding::Unix,
+            normalize_line_endings: true,
+            null_data: false,
+            encoding: InputEncoding::Utf8,
+            allow_binary: false,
+            color: ColorPolicy::Always,
         }
     }
 }
 
+/// When to keep ANSI escape sequences in output
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ColorPolicy {
+    /// Keep colors only when writing to a terminal
+    Auto,
+    /// Always pass colors through (rolo's traditional behavior)
+    Always,
+    /// Always strip colors
+    Never,
+}
+
+impl ColorPolicy {
+    /// Parse a CLI name: `auto`, `always`, `never`
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "auto" => Some(ColorPolicy::Auto),
+            "always" | "yes" => Some(ColorPolicy::Always),
+            "never" | "no" => Some(ColorPolicy::Never),
+            _ => None,
+        }
+    }
+
+    /// Whether colors should be stripped for a target that is or isn't a terminal
+    pub fn strips_for(&self, is_tty: bool) -> bool {
+        match self {
+            ColorPolicy::Auto => !is_tty,
+            ColorPolicy::Always => false,
+            ColorPolicy::Never => true,
+        }
+    }
+}
+
+/// Input decoding modes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InputEncoding {
+    /// Strict UTF-8; invalid input is an error
+    Utf8,
+    /// UTF-8 with invalid sequences replaced by U+FFFD
+    Lossy,
+    /// ISO-8859-1, where every byte maps to the code point of the same value
+    Latin1,
+}
+
+impl InputEncoding {
+    /// Parse a CLI name: `utf8`, `lossy`, `latin1`
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "utf8" | "utf-8" => Some(InputEncoding::Utf8),
+            "lossy" => Some(InputEncoding::Lossy),
+            "latin1" | "latin-1" | "iso-8859-1" => Some(InputEncoding::Latin1),
+            _ => None,
+        }
+    }
+}
+
+/// Decoded input text and how many bytes had to be replaced
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedInput {
+    pub text: String,
+    /// Invalid bytes replaced with U+FFFD (always 0 for strict and Latin-1 decoding)
+    pub replaced_bytes: usize,
+}
+
+/// Decode raw input bytes using the given encoding
+pub fn decode_input(bytes: Vec<u8>, encoding: InputEncoding) -> StreamResult<DecodedInput> {
+    let (text, replaced_bytes) = match encoding {
+        InputEncoding::Utf8 => (String::from_utf8(bytes).map_err(StreamError::from)?, 0),
+        InputEncoding::Lossy => match String::from_utf8(bytes) {
+            Ok(text) => (text, 0),
+            Err(err) => decode_utf8_lossy(err.as_bytes()),
+        },
+        InputEncoding::Latin1 => (bytes.iter().map(|&b| b as char).collect(), 0),
+    };
+
+    Ok(DecodedInput { text, replaced_bytes })
+}
+
 /// Line ending styles
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum LineEnding {
     Unix,    // \n
     Windows, // \r\n
     Mac,     // \r
+    Nul,     // \0
 }
 
 impl LineEnding {
@@ -43,8 +141,154 @@ impl LineEnding {
             LineEnding::Unix => "\n",
             LineEnding::Windows => "\r\n",
             LineEnding::Mac => "\r",
+            LineEnding::Nul => "\0",
         }
     }
+
+    /// Parse a CLI name: `lf`/`unix`, `crlf`/`windows`/`dos`, `cr`/`mac`, `nul`
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "lf" | "unix" => Some(LineEnding::Unix),
+            "crlf" | "windows" | "dos" => Some(LineEnding::Windows),
+            "cr" | "mac" => Some(LineEnding::Mac),
+            "nul" | "null" => Some(LineEnding::Nul),
+            _ => None,
+        }
+    }
+
+    /// Detect the line ending style of `content` from its first line break
+    pub fn detect(content: &str) -> Option<Self> {
+        let pos = content.find(['\r', '\n'])?;
+        let bytes = content.as_bytes();
+        Some(match (bytes[pos], bytes.get(pos + 1)) {
+            (b'\r', Some(b'\n')) => LineEnding::Windows,
+            (b'\r', _) if !content.contains('\n') => LineEnding::Mac,
+            _ => LineEnding::Unix,
+        })
+    }
+}
+
+/// Convert CRLF line endings (and bare CR, for CR-only input) to `\n`
+///
+/// Bare `\r` is only treated as a line break when the content has no `\n` at
+/// all, so carriage returns used for in-line effects survive.
+pub fn normalize_line_endings(content: &str) -> String {
+    if !content.contains('\r') {
+        return content.to_string();
+    }
+
+    let unix = content.replace("\r\n", "\n");
+    if LineEnding::detect(content) == Some(LineEnding::Mac) {
+        unix.replace('\r', "\n")
+    } else {
+        unix
+    }
+}
+
+/// Convert the `\n` line breaks of rendered output to `ending`
+///
+/// Only `\n` is translated: a CR inside a cell is content and stays as it
+/// is. Apply it once, to text with `\n` line breaks.
+pub fn apply_line_ending(content: &str, ending: LineEnding) -> String {
+    match ending {
+        LineEnding::Unix => content.to_string(),
+        _ => content.replace('\n', ending.as_str()),
+    }
+}
+
+/// Get decoded input ready for layout: normalize line endings, then escape
+///
+/// Line endings come first so that with `allow_binary` the CRs of CRLF input
+/// are removed rather than shown as `^M`. NUL-delimited records may
+/// legitimately contain CR characters and are not normalized.
+pub fn prepare_text(text: &str, config: &StreamConfig) -> String {
+    let text = if config.normalize_line_endings && !config.null_data {
+        normalize_line_endings(text)
+    } else {
+        text.to_string()
+    };
+
+    if config.allow_binary {
+        escape_records(&text, config)
+    } else {
+        text
+    }
+}
+
+/// Escape control characters record by record, keeping NUL record separators
+pub(crate) fn escape_records(text: &str, config: &StreamConfig) -> String {
+    if config.null_data {
+        text.split('\0').map(escape_control_chars).collect::<Vec<_>>().join("\0")
+    } else {
+        escape_control_chars(text)
+    }
+}
+
+/// Split input into records: NUL-delimited in `null_data` mode, lines otherwise
+///
+/// Empty NUL records (such as after the final terminator) are dropped.
+pub fn split_records<'a>(content: &'a str, config: &StreamConfig) -> Vec<&'a str> {
+    if config.null_data {
+        content.split('\0').filter(|record| !record.is_empty()).collect()
+    } else {
+        content.lines().collect()
+    }
+}
+
+/// Refuse binary-looking input unless the config allows it
+///
+/// NUL-delimited input is never treated as binary.
+pub fn check_binary(content: &[u8], config: &StreamConfig) -> StreamResult<()> {
+    if config.allow_binary || config.null_data {
+        return Ok(());
+    }
+    match binary_evidence(content) {
+        Some(reason) => Err(StreamError::BinaryInput(reason)),
+        None => Ok(()),
+    }
+}
+
+/// Make stray control characters visible so they can't drive the terminal
+///
+/// C0 controls become caret notation (`^@`, `^G`, `^?` for DEL) and C1
+/// controls become `\xNN`. Newlines, tabs and SGR color sequences are kept
+/// so layout and colors still work.
+pub fn escape_control_chars(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    let mut chars = text.char_indices();
+
+    while let Some((pos, ch)) = chars.next() {
+        if ch == '\x1b' {
+            if let Some(len) = sgr_sequence_len(&text[pos..]) {
+                // SGR sequences are ASCII: skip the rest of it char by char
+                escaped.push_str(&text[pos..pos + len]);
+                for _ in 1..len {
+                    chars.next();
+                }
+                continue;
+            }
+        }
+
+        match ch {
+            '\n' | '\t' => escaped.push(ch),
+            '\x00'..='\x1f' => {
+                escaped.push('^');
+                escaped.push((ch as u8 + b'@') as char);
+            }
+            '\x7f' => escaped.push_str("^?"),
+            '\u{80}'..='\u{9f}' => escaped.push_str(&format!("\\x{:02x}", ch as u32)),
+            _ => escaped.push(ch),
+        }
+    }
+
+    escaped
+}
+
+/// Length of the SGR sequence (`ESC [ params m`) at the start of `text`
+fn sgr_sequence_len(text: &str) -> Option<usize> {
+    let params = text.strip_prefix("\x1b[")?;
+    let end = params.find(|c: char| !(c.is_ascii_digit() || c == ';'))?;
+    (params.as_bytes()[end] == b'm').then_some(end + 3)
 }
 
 /// Read all input from stdin with buffering and error handling
@@ -54,6 +298,14 @@ pub fn read_stdin() -> StreamResult<String> {
 
 /// Read stdin with custom configuration
 pub fn read_stdin_with_config(config: &StreamConfig) -> StreamResult<String> {
+    read_stdin_decoded(config).map(|decoded| decoded.text)
+}
+
+/// Read stdin with custom configuration, reporting replaced bytes
+///
+/// Use this instead of [`read_stdin_with_config`] to tell the user how much
+/// of the input could not be decoded in [`InputEncoding::Lossy`] mode.
+pub fn read_stdin_decoded(config: &StreamConfig) -> StreamResult<DecodedInput> {
     let stdin = io::stdin();
     let reader = BufReader::new(stdin.lock());
     let mut buffer = Vec::new();
@@ -63,8 +315,12 @@ pub fn read_stdin_with_config(config: &StreamConfig) -> StreamResult<String> {
           .read_to_end(&mut buffer)
           .map_err(StreamError::from)?;
 
-    // Convert to string with UTF-8 validation
-    String::from_utf8(buffer).map_err(StreamError::from)
+    check_binary(&buffer, config)?;
+
+    // Convert to string using the configured encoding
+    let mut decoded = decode_input(buffer, config.encoding)?;
+    decoded.text = prepare_text(&decoded.text, config);
+    Ok(decoded)
 }
 
 /// Write content to stdout with error handling
@@ -73,24 +329,60 @@ pub fn write_stdout(content: &str) -> StreamResult<()> {
 }
 
 /// Write to stdout with custom configuration
+///
+/// `\n` line breaks are converted to the configured line ending. With
+/// `handle_sigpipe` set, a closed pipe (e.g. `| head`) is not an error.
 pub fn write_stdout_with_config(content: &str, config: &StreamConfig) -> StreamResult<()> {
-    let mut stdout = io::stdout();
-
-    // Handle SIGPIPE gracefully if configured
-    if config.handle_sigpipe {
-        if let Err(e) = stdout.write_all(content.as_bytes()) {
-            if e.kind() == io::ErrorKind::BrokenPipe {
-                // Gracefully handle pipe break (e.g., head command)
-                return Ok(());
-            }
-            return Err(StreamError::from(e));
-        }
-    } else {
-        stdout.write_all(content.as_bytes()).map_err(StreamError::from)?;
+    let mut out = OutputWriter::stdout(config);
+    let result = out.write_str(content).and_then(|_| out.flush());
+
+    match result {
+        Err(e) if config.handle_sigpipe && is_broken_pipe(&e) => Ok(()),
+        other => other,
     }
+}
+
+/// Write content to a file atomically
+///
+/// The content goes to a temporary file in the same directory, which is then
+/// renamed over `path`, so readers never see a half-written file. Line endings
+/// follow the config, and the color policy treats the file as a non-TTY
+/// target. An existing file's permissions are kept.
+pub fn write_file_atomic(path: &Path, content: &str, config: &StreamConfig) -> StreamResult<()> {
+    let io_error = |e: io::Error| StreamError::IoError(format!("Failed to write '{}': {}", path.display(), e));
+
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| StreamError::IoError(format!("Not a file path: '{}'", path.display())))?;
+    let dir = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    let temp_path = dir.join(format!(".{}.rolo-{}.tmp", file_name.to_string_lossy(), std::process::id()));
+
+    let result = (|| {
+        let file = OpenOptions::new().write(true).create_new(true).open(&temp_path).map_err(io_error)?;
+        if let Ok(metadata) = fs::metadata(path) {
+            file.set_permissions(metadata.permissions()).map_err(io_error)?;
+        }
 
-    stdout.flush().map_err(StreamError::from)?;
-    Ok(())
+        // The writer reports failures as stdout errors; name the file instead
+        let mut out = OutputWriter::new(file, config);
+        let file = out.write_str(content).and_then(|_| out.into_inner()).map_err(|e| match e {
+            StreamError::StdoutWrite(msg) | StreamError::PipeBroken(msg) => {
+                StreamError::IoError(format!("Failed to write '{}': {}", path.display(), msg))
+            }
+            other => other,
+        })?;
+        file.sync_all().map_err(io_error)?;
+
+        fs::rename(&temp_path, path).map_err(io_error)
+    })();
+
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    result
 }
 
 /// Create RSB Stream from stdin
@@ -177,12 +469,16 @@ pub fn create_pipeline() -> Pipeline {
 /// Builder for stream processing pipelines
 pub struct Pipeline {
     config: StreamConfig,
+    pre_commands: Vec<String>,
+    post_commands: Vec<String>,
 }
 
 impl Pipeline {
     pub fn new() -> Self {
         Self {
             config: StreamConfig::default(),
+            pre_commands: Vec::new(),
+            post_commands: Vec::new(),
         }
     }
 
@@ -206,15 +502,138 @@ impl Pipeline {
         self
     }
 
+    /// Treat input as NUL-delimited records instead of lines
+    pub fn null_data(mut self, enabled: bool) -> Self {
+        self.config.null_data = enabled;
+        self
+    }
+
+    /// Pipe input through an external command before the transform
+    ///
+    /// Stages run in the order they are added.
+    pub fn pre(mut self, command: &str) -> Self {
+        self.pre_commands.push(command.to_string());
+        self
+    }
+
+    /// Pipe output through an external command after the transform
+    ///
+    /// Stages run in the order they are added.
+    pub fn post(mut self, command: &str) -> Self {
+        self.post_commands.push(command.to_string());
+        self
+    }
+
+    /// Run `input` through the pre stages, the transform and the post stages
+    pub fn process<F>(&self, input: &str, transform: F) -> StreamResult<String>
+    where
+        F: FnOnce(Stream) -> Stream,
+    {
+        Ok(apply_line_ending(&self.render(input, transform)?, self.config.line_ending))
+    }
+
+    /// Output of [`Pipeline::process`] with `\n` line breaks
+    fn render<F>(&self, input: &str, transform: F) -> StreamResult<String>
+    where
+        F: FnOnce(Stream) -> Stream,
+    {
+        let input = filter_through_commands(&self.pre_commands, input.to_string())?;
+        let output_stream = transform(Stream::from_string(&input));
+        filter_through_commands(&self.post_commands, output_stream.to_string())
+    }
+
     /// Execute the pipeline with a transform function
     pub fn execute<F>(self, transform: F) -> StreamResult<()>
     where
         F: FnOnce(Stream) -> Stream,
     {
         let input = read_stdin_with_config(&self.config)?;
-        let stream = Stream::from_string(&input);
-        let output_stream = transform(stream);
-        write_stdout_with_config(&output_stream.to_string(), &self.config)
+        let output = self.render(&input, transform)?;
+        write_stdout_with_config(&output, &self.config)
+    }
+
+    /// Start a typed multi-stage pipeline; the first stage receives input lines
+    ///
+    /// A stage is any `FnMut(In) -> StreamResult<Out>`; see [`super::stages`]
+    /// for built-in parsing, filtering, sorting and layout stages.
+    pub fn stage<F, Out>(self, stage: F) -> StagedPipeline<Out>
+    where
+        F: FnMut(Lines) -> StreamResult<Out> + 'static,
+    {
+        StagedPipeline {
+            pipeline: self,
+            run: Box::new(stage),
+        }
+    }
+}
+
+/// Input or output lines flowing through a typed pipeline
+pub type Lines = Vec<String>;
+
+/// Delimited records (lines split into fields)
+pub type Records = Vec<Vec<String>>;
+
+/// Typed pipeline built with [`Pipeline::stage`]
+///
+/// `T` is the output type of the last stage, e.g. lines → records → table
+/// model → rendered lines. Only a pipeline that ends in [`Lines`] can be
+/// executed against stdin/stdout.
+pub struct StagedPipeline<T> {
+    pipeline: Pipeline,
+    run: Box<dyn FnOnce(Lines) -> StreamResult<T>>,
+}
+
+impl<T: 'static> StagedPipeline<T> {
+    /// Append a stage consuming the previous stage's output
+    pub fn stage<F, Out>(self, mut stage: F) -> StagedPipeline<Out>
+    where
+        F: FnMut(T) -> StreamResult<Out> + 'static,
+    {
+        let previous = self.run;
+        StagedPipeline {
+            pipeline: self.pipeline,
+            run: Box::new(move |lines| stage(previous(lines)?)),
+        }
+    }
+
+    /// Run the stages on already-split input lines
+    pub fn run_lines(self, lines: Lines) -> StreamResult<T> {
+        (self.run)(lines)
+    }
+}
+
+impl StagedPipeline<Lines> {
+    /// Run `input` through pre commands, the stages and post commands
+    ///
+    /// Output lines are joined with the configured line ending.
+    pub fn process(self, input: &str) -> StreamResult<String> {
+        let ending = self.pipeline.config.line_ending;
+        Ok(apply_line_ending(&self.render(input)?, ending))
+    }
+
+    /// Output of [`StagedPipeline::process`] with `\n` line breaks
+    fn render(self, input: &str) -> StreamResult<String> {
+        let input = filter_through_commands(&self.pipeline.pre_commands, input.to_string())?;
+        let lines = split_records(&input, &self.pipeline.config)
+            .into_iter()
+            .map(|record| record.to_string())
+            .collect();
+
+        let output_lines = (self.run)(lines)?;
+        let mut output = output_lines.join("\n");
+        if !output.is_empty() {
+            output.push('\n');
+        }
+
+        filter_through_commands(&self.pipeline.post_commands, output)
+    }
+
+    /// Read stdin, run the pipeline and write stdout, honoring the stream config
+    pub fn execute(self) -> StreamResult<()> {
+        let config = self.pipeline.config.clone();
+        let input = read_stdin_with_config(&config)?;
+        let output = self.render(&input)?;
+        write_stdout_with_config(&output, &config)
     }
 }
 
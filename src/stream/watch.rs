@@ -0,0 +1,224 @@
+//! Watch mode - periodically re-run a command and redraw its layout
+//!
+//! A flicker-free, color-preserving alternative to `watch -n2 'cmd | rolo table'`.
+//! Each tick runs the command, lays out its stdout and redraws the frame in
+//! place, highlighting cells that changed since the previous tick.
+
+use super::command::run_command;
+use super::error::StreamError;
+use super::helpers::{is_tty_output, LiveRegion};
+use super::output::OutputWriter;
+use super::utils::{LineEnding, StreamConfig};
+use crate::width::check_terminal_resize;
+use std::thread;
+use std::time::Duration;
+
+/// Reverse video on/off, used to mark changed cells without affecting width
+const HIGHLIGHT_ON: &str = "\x1b[7m";
+const HIGHLIGHT_OFF: &str = "\x1b[27m";
+
+/// Configuration for watch mode
+#[derive(Debug, Clone)]
+pub struct WatchConfig {
+    /// Shell command line to run each tick
+    pub command: String,
+    /// Delay between runs
+    pub interval: Duration,
+    /// Highlight cells that changed since the previous tick
+    pub highlight: bool,
+    /// Separator between cells in rendered lines (e.g. " | " for tables);
+    /// whitespace-separated words are compared when unset
+    pub cell_separator: Option<String>,
+    /// Redraw in place with cursor control (defaults to stdout being a TTY)
+    pub in_place: bool,
+    /// Line ending for append-only output; in-place frames always use `\n`
+    pub line_ending: LineEnding,
+}
+
+impl WatchConfig {
+    pub fn new(command: &str) -> Self {
+        let in_place = is_tty_output();
+        Self {
+            command: command.to_string(),
+            interval: Duration::from_secs(2),
+            highlight: in_place,
+            cell_separator: None,
+            in_place,
+            line_ending: LineEnding::Unix,
+        }
+    }
+}
+
+/// Run the configured command forever, redrawing its rendered output each tick
+///
+/// `render` lays out the command's stdout. Runs until the process is
+/// interrupted or an error occurs.
+pub fn watch_command<F, E>(config: &WatchConfig, mut render: F) -> Result<(), E>
+where
+    F: FnMut(&str) -> Result<String, E>,
+    E: From<StreamError>,
+{
+    let mut region = LiveRegion::new();
+    let stream = StreamConfig { line_ending: config.line_ending, ..StreamConfig::default() };
+    let mut out = OutputWriter::stdout(&stream);
+    let mut previous: Option<String> = None;
+
+    // Prime resize detection so the first tick doesn't force a full clear
+    let _ = check_terminal_resize();
+
+    loop {
+        let output = run_command(&config.command)?;
+        let rendered = render(&output.stdout)?;
+
+        let body = match (&previous, config.highlight) {
+            (Some(prev), true) => highlight_changes(prev, &rendered, config.cell_separator.as_deref()),
+            _ => rendered.clone(),
+        };
+
+        let mut frame = format!("Every {}: {}", format_interval(config.interval), config.command);
+        if !output.success() {
+            frame.push_str(&format!("  (exit {})", output.exit_code));
+        }
+        frame.push_str("\n\n");
+        frame.push_str(&body);
+        if !output.stderr.trim().is_empty() {
+            frame.push_str("\n\n");
+            frame.push_str(output.stderr.trim_end());
+        }
+
+        if config.in_place {
+            let resized = check_terminal_resize().is_some();
+            region.redraw(&mut out, &frame, resized)?;
+        } else {
+            out.write_str(&format!("{}\n\n", frame))?;
+            out.flush()?;
+        }
+
+        previous = Some(rendered);
+        thread::sleep(config.interval);
+    }
+}
+
+/// Mark cells of `current` that differ from the same position in `previous`
+///
+/// Lines are compared by index; cells within a line are split on
+/// `cell_separator`, or into whitespace-separated words when it is `None`.
+/// Lines that did not exist before are highlighted entirely.
+pub fn highlight_changes(previous: &str, current: &str, cell_separator: Option<&str>) -> String {
+    let previous_lines: Vec<&str> = previous.lines().collect();
+
+    current
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let prev_line = previous_lines.get(i).copied();
+            if prev_line == Some(line) {
+                return line.to_string();
+            }
+            let prev_line = prev_line.unwrap_or("");
+
+            match cell_separator {
+                Some(sep) => {
+                    let prev_cells: Vec<&str> = prev_line.split(sep).collect();
+                    line.split(sep)
+                        .enumerate()
+                        .map(|(j, cell)| highlight_if_changed(cell, prev_cells.get(j).copied()))
+                        .collect::<Vec<_>>()
+                        .join(sep)
+                }
+                None => {
+                    let prev_words = split_words(prev_line);
+                    split_words(line)
+                        .into_iter()
+                        .enumerate()
+                        .map(|(j, word)| highlight_if_changed(word, prev_words.get(j).copied()))
+                        .collect()
+                }
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Parse an interval such as `2`, `2s`, `500ms`, `1.5s` or `1m`
+pub fn parse_interval(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    let (number, scale) = if let Some(n) = value.strip_suffix("ms") {
+        (n, 0.001)
+    } else if let Some(n) = value.strip_suffix('s') {
+        (n, 1.0)
+    } else if let Some(n) = value.strip_suffix('m') {
+        (n, 60.0)
+    } else {
+        (value, 1.0)
+    };
+
+    match number.trim().parse::<f64>() {
+        Ok(n) if n > 0.0 && n.is_finite() => Some(Duration::from_secs_f64(n * scale)),
+        _ => None,
+    }
+}
+
+/// Human-readable interval for the frame header
+fn format_interval(interval: Duration) -> String {
+    if interval.subsec_millis() == 0 {
+        format!("{}s", interval.as_secs())
+    } else {
+        format!("{}s", interval.as_secs_f64())
+    }
+}
+
+/// Wrap a cell in reverse video if it differs from its previous value
+///
+/// Surrounding padding is kept outside the highlight so the cell still reads
+/// as aligned text.
+fn highlight_if_changed(cell: &str, previous: Option<&str>) -> String {
+    let content = cell.trim();
+    if previous == Some(cell) || content.is_empty() {
+        return cell.to_string();
+    }
+
+    let leading = &cell[..cell.len() - cell.trim_start().len()];
+    let trailing = &cell[cell.trim_end().len()..];
+    format!("{}{}{}{}{}", leading, HIGHLIGHT_ON, content, HIGHLIGHT_OFF, trailing)
+}
+
+/// Split a line into alternating runs of whitespace and non-whitespace
+fn split_words(line: &str) -> Vec<&str> {
+    let mut words = Vec::new();
+    let mut start = 0;
+    let mut in_space = None;
+
+    for (pos, ch) in line.char_indices() {
+        let space = ch.is_whitespace();
+        if in_space != Some(space) {
+            if pos > start {
+                words.push(&line[start..pos]);
+            }
+            start = pos;
+            in_space = Some(space);
+        }
+    }
+    if start < line.len() {
+        words.push(&line[start..]);
+    }
+
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_words_keeps_whitespace_runs() {
+        assert_eq!(split_words("ab  cd e"), vec!["ab", "  ", "cd", " ", "e"]);
+        assert!(split_words("").is_empty());
+    }
+
+    #[test]
+    fn test_format_interval() {
+        assert_eq!(format_interval(Duration::from_secs(2)), "2s");
+        assert_eq!(format_interval(Duration::from_millis(1500)), "1.5s");
+    }
+}
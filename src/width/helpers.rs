@@ -2,7 +2,45 @@
 
 #![allow(dead_code)] // Helper functions for future width calculation features
 
-pub(crate) fn strip_ansi_basic(_text: &str) -> String {
-    // TODO: Implement in TASK-002
-    String::new()
+/// Remove ANSI escape sequences without external dependencies
+///
+/// Handles CSI sequences (`ESC [ ... final`), OSC sequences terminated by BEL
+/// or `ESC \`, and two-character escapes.
+pub(crate) fn strip_ansi_basic(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        if ch != '\x1b' {
+            result.push(ch);
+            continue;
+        }
+
+        match chars.next() {
+            // CSI: parameters and intermediates up to a final byte in @..~
+            Some('[') => {
+                for c in chars.by_ref() {
+                    if ('@'..='~').contains(&c) {
+                        break;
+                    }
+                }
+            }
+            // OSC: up to BEL or ST (ESC \)
+            Some(']') => {
+                while let Some(c) = chars.next() {
+                    if c == '\x07' {
+                        break;
+                    }
+                    if c == '\x1b' && chars.peek() == Some(&'\\') {
+                        chars.next();
+                        break;
+                    }
+                }
+            }
+            // Two-character escape (or a lone ESC at the end)
+            _ => {}
+        }
+    }
+
+    result
 }
\ No newline at end of file
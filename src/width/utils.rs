@@ -1,6 +1,7 @@
 //! Width calculation utilities per MODULE_SPEC
 
 use crate::width::error::WidthError;
+use crate::width::helpers::strip_ansi_basic;
 
 // Use libc for terminal detection on Unix systems
 #[cfg(all(unix, feature = "libc"))]
@@ -9,6 +10,13 @@ use libc;
 #[cfg(feature = "width-boxy")]
 use crate::width::width_boxy_adapter;
 
+/// How [`get_display_width`] measures text in this build
+#[cfg(feature = "width-boxy")]
+pub const WIDTH_STRATEGY: &str = "boxy (unicode-width display columns, ANSI stripped)";
+/// How [`get_display_width`] measures text in this build
+#[cfg(not(feature = "width-boxy"))]
+pub const WIDTH_STRATEGY: &str = "basic (one column per character)";
+
 /// Calculate display width of text
 /// Delegates to boxy adapter when available, fallback otherwise
 pub fn get_display_width(text: &str) -> Result<usize, WidthError> {
@@ -23,6 +31,11 @@ pub fn get_display_width(text: &str) -> Result<usize, WidthError> {
     }
 }
 
+/// Remove ANSI escape sequences (colors, cursor movement) from text
+pub fn strip_ansi(text: &str) -> String {
+    strip_ansi_basic(text)
+}
+
 /// Get terminal width with enhanced detection
 /// Integrates RSB host module for robust environment detection
 pub fn get_terminal_width() -> usize {
@@ -39,45 +52,66 @@ pub fn get_terminal_width() -> usize {
 /// RSB-based terminal width detection (fallback implementation)
 /// Provides robust width detection using multiple methods
 pub fn get_terminal_width_rsb() -> usize {
+    get_terminal_width_source_rsb().0
+}
+
+/// Terminal width together with the method that produced it
+///
+/// The source is `COLUMNS`, `ioctl`, `tput`, the other environment variable
+/// that was used, `default` when nothing usable was found, or `boxy` when
+/// the boxy adapter measures the terminal.
+pub fn get_terminal_width_with_source() -> (usize, &'static str) {
+    #[cfg(feature = "width-boxy")]
+    {
+        (width_boxy_adapter::get_terminal_width(), "boxy")
+    }
+    #[cfg(not(feature = "width-boxy"))]
+    {
+        get_terminal_width_source_rsb()
+    }
+}
+
+fn get_terminal_width_source_rsb() -> (usize, &'static str) {
     // Method 1: Check COLUMNS environment variable (set by shell)
-    if let Ok(var) = std::env::var("COLUMNS") {
-        if let Ok(width) = var.parse::<usize>() {
-            if width >= 10 && width <= 500 {
-                return width;
-            }
-        }
+    if let Some(width) = env_width("COLUMNS") {
+        return (width, "COLUMNS");
     }
 
     // Method 2: Query terminal via ioctl (Unix-like systems)
     #[cfg(all(unix, feature = "libc"))]
     {
-        if let Some(width) = get_terminal_width_ioctl() {
-            if width >= 10 && width <= 500 {
-                return width;
-            }
+        if let Some(width) = get_terminal_width_ioctl().filter(|&width| plausible_width(width)) {
+            return (width, "ioctl");
         }
     }
 
     // Method 3: Try tput command
-    if let Some(width) = get_terminal_width_tput() {
-        if width >= 10 && width <= 500 {
-            return width;
-        }
+    if let Some(width) = get_terminal_width_tput().filter(|&width| plausible_width(width)) {
+        return (width, "tput");
     }
 
     // Method 4: Check other common environment variables
-    for var_name in &["TERM_WIDTH", "WIDTH", "TERMWIDTH"] {
-        if let Ok(var) = std::env::var(var_name) {
-            if let Ok(width) = var.parse::<usize>() {
-                if width >= 10 && width <= 500 {
-                    return width;
-                }
-            }
+    for var_name in ["TERM_WIDTH", "WIDTH", "TERMWIDTH"] {
+        if let Some(width) = env_width(var_name) {
+            return (width, var_name);
         }
     }
 
     // Default fallback
-    80
+    (80, "default")
+}
+
+/// Width from an environment variable, if it holds a plausible one
+fn env_width(var_name: &str) -> Option<usize> {
+    std::env::var(var_name)
+        .ok()
+        .and_then(|var| var.parse::<usize>().ok())
+        .filter(|&width| plausible_width(width))
+}
+
+/// Detected widths outside 10-500 are treated as bogus
+fn plausible_width(width: usize) -> bool {
+    (10..=500).contains(&width)
 }
 
 /// Detect terminal width using ioctl system call (Unix-like systems)
@@ -169,6 +203,12 @@ pub fn check_terminal_resize() -> Option<(usize, usize)> {
     None
 }
 
+/// Smallest width accepted by [`validate_width`]
+pub const MIN_WIDTH: usize = 10;
+
+/// Largest width accepted by [`validate_width`]
+pub const MAX_WIDTH: usize = 200;
+
 /// Validate width input
 pub fn validate_width(width_str: &str) -> Result<usize, WidthError> {
     #[cfg(feature = "width-boxy")]
@@ -178,8 +218,8 @@ pub fn validate_width(width_str: &str) -> Result<usize, WidthError> {
     #[cfg(not(feature = "width-boxy"))]
     {
         match width_str.parse::<usize>() {
-            Ok(w) if w >= 10 && w <= 200 => Ok(w),
-            Ok(w) => Err(WidthError::InvalidRange(w, 10, 200)),
+            Ok(w) if (MIN_WIDTH..=MAX_WIDTH).contains(&w) => Ok(w),
+            Ok(w) => Err(WidthError::InvalidRange(w, MIN_WIDTH, MAX_WIDTH)),
             Err(_) => Err(WidthError::InvalidInput(format!("Width must be a number: {}", width_str))),
         }
     }
@@ -2,6 +2,7 @@
 //! Adapts boxy's width calculation logic with RSB MODULE_SPEC patterns
 
 use crate::width::error::WidthError;
+use crate::width::utils::{MAX_WIDTH, MIN_WIDTH};
 use std::process::{Command, Stdio};
 use std::fs::File;
 
@@ -101,8 +102,8 @@ fn get_display_width_fallback(text: &str) -> Result<usize, WidthError> {
 /// Validate width input (from boxy)
 pub fn validate_width(width_str: &str) -> Result<usize, WidthError> {
     match width_str.parse::<usize>() {
-        Ok(w) if w >= 10 && w <= 200 => Ok(w),
-        Ok(w) => Err(WidthError::InvalidRange(w, 10, 200)),
+        Ok(w) if (MIN_WIDTH..=MAX_WIDTH).contains(&w) => Ok(w),
+        Ok(w) => Err(WidthError::InvalidRange(w, MIN_WIDTH, MAX_WIDTH)),
         Err(_) => Err(WidthError::InvalidInput(format!("Width must be a number: {}", width_str))),
     }
 }
@@ -2,46 +2,86 @@
 //!
 //! Tests functionality that requires the json-support feature
 
+use rololib::cli::render_layout;
+use rololib::prelude::*;
+use std::fs;
+
+fn argv(words: &[&str]) -> Vec<String> {
+    std::iter::once("rolo").chain(words.iter().copied()).map(String::from).collect()
+}
+
+fn fixture(name: &str) -> String {
+    fs::read_to_string(format!("tests/data/{}", name)).unwrap()
+}
+
+fn json_model(text: &str) -> TableModel {
+    parse_table(text, InputFormat::Json, "\t").unwrap()
+}
+
+fn strings(cells: &[&str]) -> Vec<String> {
+    cells.iter().map(|cell| cell.to_string()).collect()
+}
+
 #[test]
 fn test_json_feature_available() {
-    // Test that JSON features are available when enabled
-    // This ensures the serde_json dependency is properly linked
+    assert!(FEATURES.contains(&"json-support"));
+    assert_eq!(InputFormat::parse("json"), Some(InputFormat::Json));
+    assert_eq!(InputFormat::parse("jsonl"), Some(InputFormat::Jsonl));
 
-    // Basic JSON functionality should be available through the feature
-    assert!(true); // Placeholder - will be updated when JSON features are implemented
+    // JSON is only used when asked for
+    assert_eq!(InputFormat::Auto.resolve("\t"), InputFormat::Delimited);
 }
 
 #[test]
 fn test_json_parsing_basic() {
-    // Test basic JSON parsing when feature is enabled
+    // A single object is a one-row table, keys in input order
     let json_data = r#"{"name": "John", "age": 30, "city": "NYC"}"#;
 
-    // When JSON support is implemented, we would test:
-    // - Parsing JSON data
-    // - Converting to table format
-    // - Handling nested objects
-    // - Flattening complex structures
-
-    assert!(!json_data.is_empty());
+    let model = json_model(json_data);
+    assert_eq!(model.headers, Some(strings(&["name", "age", "city"])));
+    assert_eq!(model.rows, vec![strings(&["John", "30", "NYC"])]);
 }
 
 #[test]
 fn test_json_array_to_table() {
-    use rololib::prelude::*;
-
-    // Test that JSON arrays can be converted to table format
     let json_array = r#"[
         {"name": "Alice", "age": 30, "city": "Boston"},
         {"name": "Bob", "age": 25, "city": "Seattle"},
         {"name": "Carol", "age": 35, "city": "Portland"}
     ]"#;
 
-    // This would use JSON parsing to create table input
-    let result = format_table("placeholder", None); // Will be updated with actual JSON input
-    assert!(result.is_ok());
+    let model = json_model(json_array);
+    assert_eq!(model.headers, Some(strings(&["name", "age", "city"])));
+    assert_eq!(model.rows.len(), 3);
+    assert_eq!(model.rows[2], strings(&["Carol", "35", "Portland"]));
 
-    // When implemented, should test that JSON objects become table rows
-    // and that object keys become table headers
+    // Rendered like the same data as TSV
+    let tsv = "name\tage\tcity\nAlice\t30\tBoston\nBob\t25\tSeattle\nCarol\t35\tPortland";
+    assert_eq!(format_table_model(&model, 80).unwrap(), format_table(tsv, "\t").unwrap());
+}
+
+#[test]
+fn test_json_union_of_keys() {
+    // Headers are every key in first-seen order; missing keys are empty cells
+    let model = json_model(&fixture("people.json"));
+    assert_eq!(model.headers, Some(strings(&["name", "age", "city", "email", "tags", "manager.name"])));
+    assert_eq!(
+        model.rows,
+        vec![
+            strings(&["Alice", "30", "Boston", "", "", ""]),
+            strings(&["Bob", "25", "", "bob@example.com", "", ""]),
+            strings(&["Carol", "", "Portland", "", "[\"admin\",\"ops\"]", "Alice"]),
+        ]
+    );
+}
+
+#[test]
+fn test_json_lines_to_table() {
+    let model = parse_table(&fixture("events.jsonl"), InputFormat::Jsonl, "\t").unwrap();
+    assert_eq!(model.headers, Some(strings(&["time", "level", "message", "disk.used", "disk.mount", "retry", "code"])));
+    assert_eq!(model.rows.len(), 3);
+    assert_eq!(model.rows[1][3..5], strings(&["91", "/"]));
+    assert_eq!(model.rows[2], strings(&["09:07", "error", "write failed", "", "", "true", ""]));
 }
 
 #[test]
@@ -60,34 +100,92 @@ fn test_json_nested_object_flattening() {
         }
     }"#;
 
-    // When implemented, should test:
-    // - Flattening nested objects with dot notation (user.name, user.details.age)
-    // - Converting to table-friendly format
-    // - Handling deep nesting gracefully
-
-    assert!(!nested_json.is_empty());
+    let flattened = |max_depth| {
+        let config = FlattenConfig { max_depth, ..FlattenConfig::default() };
+        parse_table_with_config(nested_json, InputFormat::Json, "\t", &config).unwrap()
+    };
+
+    // Nested objects become dot-path columns
+    let model = json_model(nested_json);
+    assert_eq!(
+        model.headers,
+        Some(strings(&["user.name", "user.details.age", "user.details.location.city", "user.details.location.state"]))
+    );
+    assert_eq!(model.rows, vec![strings(&["John", "30", "NYC", "NY"])]);
+    assert_eq!(flattened(None), model);
+
+    // Deeper values stay compact JSON
+    let model = flattened(Some(1));
+    assert_eq!(model.headers, Some(strings(&["user.name", "user.details"])));
+    assert_eq!(model.rows[0][1], "{\"age\":30,\"location\":{\"city\":\"NYC\",\"state\":\"NY\"}}");
+    assert_eq!(flattened(Some(0)).headers, Some(strings(&["user"])));
+
+    // Same through the command line
+    let config = parse_args(&argv(&["table", "--format", "json", "--max-depth", "2"])).unwrap();
+    let table = render_layout(&config, nested_json).unwrap();
+    assert!(table.starts_with("user.name | user.details.age | user.details.location"), "{}", table);
 }
 
 #[test]
 fn test_json_array_handling() {
-    // Test handling of JSON arrays within objects
+    // Arrays inside objects are shown as compact JSON, in order
     let json_with_arrays = r#"{
         "name": "John",
         "hobbies": ["reading", "coding", "hiking"],
-        "scores": [85, 90, 78]
+        "scores": [85, 90, 78],
+        "mixed": [1, "two", null, [3]]
     }"#;
 
-    // When implemented, should test:
-    // - Converting arrays to comma-separated strings
-    // - Handling mixed-type arrays
-    // - Preserving array order
+    let model = json_model(json_with_arrays);
+    assert_eq!(
+        model.rows,
+        vec![strings(&["John", "[\"reading\",\"coding\",\"hiking\"]", "[85,90,78]", "[1,\"two\",null,[3]]"])]
+    );
+
+    let with_arrays = |arrays| {
+        let config = FlattenConfig { arrays, ..FlattenConfig::default() };
+        parse_table_with_config(json_with_arrays, InputFormat::Json, "\t", &config).unwrap()
+    };
+
+    // Indexing gives every element its own column
+    let model = with_arrays(ArrayStyle::Index);
+    assert_eq!(
+        model.headers,
+        Some(strings(&[
+            "name", "hobbies[0]", "hobbies[1]", "hobbies[2]", "scores[0]", "scores[1]", "scores[2]",
+            "mixed[0]", "mixed[1]", "mixed[2]", "mixed[3][0]",
+        ]))
+    );
+    assert_eq!(model.rows[0][1..4], strings(&["reading", "coding", "hiking"]));
+
+    // Joining keeps one cell per array
+    let model = with_arrays(ArrayStyle::Join);
+    assert_eq!(model.rows, vec![strings(&["John", "reading, coding, hiking", "85, 90, 78", "1, two, , [3]"])]);
+}
+
+#[test]
+fn test_json_field_selection() {
+    let nested = r#"[
+        {"id": 1, "user": {"name": "Ann", "address": {"city": "Oslo", "zip": "0150"}}, "tags": ["a", "b"]},
+        {"id": 2, "user": {"name": "Ben"}, "tags": ["c"]}
+    ]"#;
 
-    assert!(!json_with_arrays.is_empty());
+    // Paths select and order columns; a prefix selects everything under it
+    let words = ["table", "--format", "json", "--arrays", "index", "--fields", "user.address.city,id,tags"];
+    let config = parse_args(&argv(&words)).unwrap();
+    let table = render_layout(&config, nested).unwrap();
+    let header: Vec<&str> = table.lines().next().unwrap().split('|').map(str::trim).collect();
+    assert_eq!(header, vec!["user.address.city", "id", "tags[0]", "tags[1]"]);
+    assert!(table.lines().nth(3).unwrap().starts_with("                  | 2  | c"), "{}", table);
+
+    let config = parse_args(&argv(&["table", "--format", "json", "--fields", "user.email"])).unwrap();
+    let err = render_layout(&config, nested).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Usage);
+    assert!(err.to_string().contains("No column matches field 'user.email'"), "{}", err);
 }
 
 #[test]
 fn test_json_special_values() {
-    // Test handling of special JSON values
     let special_json = r#"{
         "string": "hello",
         "number": 42,
@@ -96,42 +194,53 @@ fn test_json_special_values() {
         "null_value": null,
         "empty_string": "",
         "empty_object": {},
-        "empty_array": []
+        "empty_array": [],
+        "big": 12345678901234567890,
+        "multiline": "one\ntwo"
     }"#;
 
-    // When implemented, should test:
-    // - Proper type conversion to strings for table display
-    // - Handling null values
-    // - Representing empty containers
+    let model = json_model(special_json);
+    assert_eq!(
+        model.rows,
+        vec![strings(&["hello", "42", "3.14", "true", "", "", "{}", "[]", "12345678901234567890", "one↵two"])]
+    );
+}
 
-    assert!(!special_json.is_empty());
+#[test]
+fn test_json_floats_and_exponents() {
+    let model = json_model(r#"[{"a": 1.0, "b": 1e300, "c": -2.5e-7, "d": 0.1, "e": [1.50, 2E3]}]"#);
+    assert_eq!(model.rows, vec![strings(&["1.0", "1e+300", "-2.5e-7", "0.1", "[1.5,2000.0]"])]);
 }
 
 #[test]
 fn test_json_integration_with_stream() {
-    use rololib::prelude::*;
-
-    // Test that JSON processing works with stream processing
-    let json_stream = rsb::prelude::Stream::from_string(r#"{"a": 1, "b": 2}"#);
-
-    // Should be able to process JSON data through streams
-    let content = json_stream.to_string();
-    assert!(content.contains("\"a\""));
-
-    // When implemented, should test stream-based JSON processing
+    for (format, file) in [("json", "people.json"), ("jsonl", "events.jsonl")] {
+        let config = parse_args(&argv(&["table", "--format", format, "--width", "120"])).unwrap();
+        let table = render_layout(&config, &fixture(file)).unwrap();
+        assert_eq!(table.lines().count(), 5, "{}", table);
+    }
+
+    // Empty input is an empty table
+    let config = parse_args(&argv(&["table", "--format", "jsonl"])).unwrap();
+    assert_eq!(render_layout(&config, "\n").unwrap(), "");
 }
 
 #[test]
 fn test_json_error_handling() {
-    // Test error handling for malformed JSON
     let malformed_json = r#"{"name": "John", "age": 30"#; // Missing closing brace
+    let err = parse_table(malformed_json, InputFormat::Json, "\t").unwrap_err();
+    assert!(matches!(err, FormatError::Malformed(_, _)));
 
-    // When implemented, should test:
-    // - Graceful handling of malformed JSON
-    // - Clear error messages for JSON parsing failures
-    // - Partial recovery when possible
+    let err = parse_table("[{\"a\": 1}, 2]", InputFormat::Json, "\t").unwrap_err();
+    assert!(err.to_string().contains("item 2 is a number, expected an object"), "{}", err);
 
-    assert!(!malformed_json.is_empty());
+    let err = parse_table("{\"a\": 1}\n\n[1]\n", InputFormat::Jsonl, "\t").unwrap_err();
+    assert!(err.to_string().contains("line 3 is an array"), "{}", err);
+
+    // Bad input is a data error, not a usage error
+    let config = parse_args(&argv(&["table", "--format", "json"])).unwrap();
+    let err = render_layout(&config, malformed_json).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Layout);
 }
 
 #[test]
@@ -149,20 +258,36 @@ fn test_json_pretty_formatting() {
 
 #[test]
 fn test_json_to_multiple_formats() {
-    use rololib::prelude::*;
-
-    // Test conversion of JSON to different output formats
+    // --format only changes how table mode reads its input
     let json_data = r#"[{"a": 1, "b": 2}, {"a": 3, "b": 4}]"#;
 
-    // Should be able to convert to different layout formats
-    let table_result = format_table("placeholder", None);
-    let list_result = format_list("placeholder", None);
-    let columns_result = format_columns("placeholder", 2, None);
+    let table = render_layout(&parse_args(&argv(&["table", "--format", "json"])).unwrap(), json_data).unwrap();
+    assert_eq!(table, format_table("a\tb\n1\t2\n3\t4", "\t").unwrap());
 
-    assert!(table_result.is_ok());
-    assert!(list_result.is_ok());
-    assert!(columns_result.is_ok());
-
-    // When implemented, should test that JSON converts appropriately
-    // to each output format
-}
\ No newline at end of file
+    let list = render_layout(&parse_args(&argv(&["list", "--format", "json"])).unwrap(), json_data).unwrap();
+    assert_eq!(list, format_list(json_data).unwrap());
+}
+#[test]
+fn test_json_dotted_key_colliding_with_nested_path() {
+    let text = r#"[{"id": 1}, {"a.b": 1, "a": {"b": 2}}]"#;
+    let err = parse_table(text, InputFormat::Json, "\t").unwrap_err();
+    assert!(matches!(&err, FormatError::DuplicateColumn(column, 2) if column == "a.b"), "{:?}", err);
+    assert!(err.to_string().contains("Object 2 has two values for column 'a.b'"), "{}", err);
+
+    let config = FlattenConfig { arrays: ArrayStyle::Index, ..FlattenConfig::default() };
+    let err = parse_table_with_config(r#"{"tags": ["x"], "tags[0]": "y"}"#, InputFormat::Json, "\t", &config).unwrap_err();
+    assert!(matches!(&err, FormatError::DuplicateColumn(column, 1) if column == "tags[0]"), "{:?}", err);
+
+    let config = parse_args(&argv(&["table", "--format", "json"])).unwrap();
+    let err = render_layout(&config, text).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Layout);
+    assert!(err.hint().unwrap().contains("--max-depth"));
+
+    // Without flattening both values keep a column of their own
+    let model = parse_table_with_config(text, InputFormat::Json, "\t", &FlattenConfig { max_depth: Some(0), ..FlattenConfig::default() }).unwrap();
+    assert_eq!(model.headers, Some(strings(&["id", "a.b", "a"])));
+    assert_eq!(model.rows[1], strings(&["", "1", "{\"b\":2}"]));
+
+    // Dotted keys that don't collide are fine
+    assert_eq!(json_model(r#"{"a.b": 1, "a": {"c": 2}}"#).headers, Some(strings(&["a.b", "a.c"])));
+}
@@ -0,0 +1,117 @@
+//! CliConfig parser feature tests - the single option parser shared by the binary and library
+
+use rololib::cli::error::EXIT_USAGE;
+use rololib::config::{ENV_VARS, NO_ENV_OPTIONS, PROFILE_ENV_VAR};
+use rololib::prelude::*;
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+fn argv(words: &[&str]) -> Vec<String> {
+    std::iter::once("rolo").chain(words.iter().copied()).map(String::from).collect()
+}
+
+#[test]
+fn test_parse_args_subcommand_and_options() {
+    let config = parse_args(&argv(&["table", "--delim", ",", "--width=60", "--crlf"])).unwrap();
+
+    assert_eq!(config.subcommand.as_deref(), Some("table"));
+    assert_eq!(config.mode, CliMode::Table);
+    assert_eq!(config.delimiter.as_deref(), Some(","));
+    assert_eq!(config.width, Some(60));
+    assert_eq!(config.layout_width(), 60);
+    assert_eq!(config.stream_config().line_ending, LineEnding::Windows);
+}
+
+#[test]
+fn test_parse_args_rejects_unknown_options_and_words() {
+    assert!(matches!(parse_args(&argv(&["--colz", "3"])), Err(CliError::InvalidArgument(_))));
+    assert!(matches!(parse_args(&argv(&["tabel"])), Err(CliError::UnsupportedCommand(_))));
+}
+
+#[test]
+fn test_parse_args_validates_values() {
+    assert!(matches!(parse_args(&argv(&["--cols", "0"])), Err(CliError::InvalidArgument(_))));
+    assert!(matches!(parse_args(&argv(&["--cols=11"])), Err(CliError::InvalidArgument(_))));
+    assert!(matches!(parse_args(&argv(&["--width", "5"])), Err(CliError::InvalidWidth(_))));
+    assert!(matches!(parse_args(&argv(&["--gap", "99"])), Err(CliError::InvalidArgument(_))));
+    assert!(matches!(parse_args(&argv(&["--eol", "lfcr"])), Err(CliError::InvalidArgument(_))));
+    assert!(matches!(parse_args(&argv(&["--interval", "soon"])), Err(CliError::InvalidArgument(_))));
+    assert!(matches!(parse_args(&argv(&["--cols"])), Err(CliError::MissingArgument(_))));
+}
+
+#[test]
+fn test_parse_args_wrapped_command() {
+    let config = parse_args(&argv(&["watch", "table", "--interval", "500ms", "--", "ps", "--cols", "x"])).unwrap();
+
+    assert_eq!(config.subcommand.as_deref(), Some("watch"));
+    assert_eq!(config.mode, CliMode::Table);
+    assert_eq!(config.interval, Duration::from_millis(500));
+    // Options after `--` belong to the command, not to rolo
+    assert_eq!(config.command, vec!["ps", "--cols", "x"]);
+    assert_eq!(config.columns, None);
+}
+
+#[test]
+fn test_parse_args_repeatable_filters_and_follow_file() {
+    let config = parse_args(&argv(&["list", "--pre", "sort", "--pre=uniq", "-f", "app.log"])).unwrap();
+
+    assert_eq!(config.pre_commands, vec!["sort", "uniq"]);
+    assert!(config.follow);
+    assert_eq!(config.follow_file.as_deref(), Some(std::path::Path::new("app.log")));
+}
+
+#[test]
+fn test_run_cli_reports_usage_errors() {
+    assert_eq!(run_cli(&argv(&["--cols", "abc"])), EXIT_USAGE);
+    assert_eq!(run_cli(&argv(&["--version"])), 0);
+}
+
+/// Run the `rolo` binary on `input`, returning its exit code and stdout
+fn run_rolo(words: &[&str], input: &str) -> (i32, String) {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_rolo"))
+        .args(words)
+        .env("XDG_CONFIG_HOME", "/nonexistent")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(input.as_bytes()).unwrap();
+    let output = child.wait_with_output().unwrap();
+    (output.status.code().unwrap_or(-1), String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[test]
+fn test_binary_accepts_options_before_subcommand() {
+    let input = "a\tb\nc\td\n";
+    let after = run_rolo(&["table", "--width", "40"], input);
+    assert_eq!(after.0, 0);
+    assert!(after.1.contains("a | b"), "{}", after.1);
+
+    assert_eq!(run_rolo(&["--width", "40", "table"], input), after);
+    assert_eq!(run_rolo(&["--width", "40", "--table"], input), after);
+    assert_eq!(run_rolo(&["--gap", "1", "columns", "--cols", "2"], "x\ny\n").0, 0);
+
+    // Every dispatched subcommand is found after options, admin commands too
+    assert!(run_rolo(&["--width", "40", "man"], "").1.starts_with(".TH ROLO 1"));
+    assert!(run_rolo(&["--width", "40", "completions", "bash"], "").1.contains("rolo"));
+    assert!(run_rolo(&["--width", "40", "help", "table"], "").1.starts_with("rolo table - "));
+    assert!(run_rolo(&["--width", "40", "version"], "").1.contains("rolo"));
+}
+
+#[test]
+fn test_help_environment_lists_every_variable() {
+    let (code, help) = run_rolo(&["--help"], "");
+    assert_eq!(code, 0);
+
+    let section = &help[help.find("ENVIRONMENT:").unwrap()..help.find("EXIT STATUS:").unwrap()];
+    let words: Vec<&str> = section.split(|c: char| c.is_whitespace() || c == ',' || c == '.').collect();
+    for (var, _) in ENV_VARS {
+        assert!(words.contains(var), "{} missing from help", var);
+    }
+    assert!(words.contains(&PROFILE_ENV_VAR));
+    for long in NO_ENV_OPTIONS {
+        assert!(words.contains(&format!("--{}", long).as_str()), "--{} exclusion missing from help", long);
+    }
+}
@@ -0,0 +1,115 @@
+//! Shell completion feature tests - scripts generated from the parser's option table
+
+use rololib::cli::options::{find_option, OptionAction, OPTIONS};
+use rololib::config::ENV_VARS;
+use rololib::prelude::*;
+
+fn argv(words: &[&str]) -> Vec<String> {
+    std::iter::once("rolo").chain(words.iter().copied()).map(String::from).collect()
+}
+
+/// A value each option accepts
+fn sample_value(long: &str) -> &'static str {
+    match long {
+        "cols" => "2",
+        "width" => "40",
+        "gap" => "1",
+        "interval" => "1s",
+        "max-depth" => "2",
+        _ => "x",
+    }
+}
+
+#[test]
+fn test_every_table_option_is_parsed() {
+    for option in OPTIONS {
+        let longs = std::iter::once(option.long).chain(option.aliases.iter().copied());
+        for flag in longs.map(|long| format!("--{}", long)).chain(option.short.map(|short| format!("-{}", short))) {
+            let value = option.values.first().copied().unwrap_or_else(|| sample_value(option.long));
+            let mut words = vec![flag.as_str()];
+            if option.value.is_some() && !option.value_optional {
+                words.push(value);
+            }
+
+            let config = parse_args(&argv(&words));
+            assert!(config.is_ok(), "{} rejected: {:?}", flag, config.err());
+            assert_eq!(find_option(&flag).map(|spec| spec.long), Some(option.long));
+        }
+    }
+}
+
+#[test]
+fn test_enumerated_values_match_parser() {
+    for option in OPTIONS {
+        if let OptionAction::Set(key) = option.action {
+            for value in option.values {
+                let mut config = CliConfig::default();
+                assert!(apply_setting(&mut config, key, value).is_ok(), "--{}={}", option.long, value);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_bash_completion_covers_commands_options_and_values() {
+    let script = completion_script(Shell::Bash);
+
+    assert!(script.contains("complete -F _rolo rolo"));
+    assert!(script.contains("compgen -W \"list table columns"));
+    for flag in ["--cols", "--delimiter", "--sep", "-n", "--null-data", "--profile", "--color"] {
+        assert!(script.contains(flag), "missing {}", flag);
+    }
+    assert!(script.contains("--list-style)\n            COMPREPLY=($(compgen -W \"numbers bullets stars dots dash\""));
+    assert!(script.contains("--align)\n            COMPREPLY=($(compgen -W \"left right center\""));
+    assert!(script.contains("completions) COMPREPLY=($(compgen -W \"bash zsh fish\""));
+}
+
+#[test]
+fn test_zsh_and_fish_completion() {
+    let zsh = completion_script(Shell::Zsh);
+    assert!(zsh.starts_with("#compdef rolo\n"));
+    assert!(zsh.contains("'--list-style=[List style (numbers, bullets, stars, dots, dash)]:STYLE:(numbers bullets stars dots dash)'"));
+    assert!(zsh.contains("'(--output -o)'{--output=,-o+}'[Write output to FILE atomically instead of stdout]:FILE:_files'"));
+    assert!(zsh.contains("'table:Format as table with delimiter detection'"));
+
+    let fish = completion_script(Shell::Fish);
+    assert!(fish.contains("complete -c rolo -l align -x -a 'left right center'"));
+    assert!(fish.contains("complete -c rolo -s n -l line-numbers -d 'Add line numbers to list mode'"));
+    assert!(fish.contains("-a list -d 'Format as list with optional line numbers'"));
+}
+
+#[test]
+fn test_completions_subcommand_arguments() {
+    let config = parse_args(&argv(&["completions", "zsh"])).unwrap();
+    assert_eq!(config.subcommand.as_deref(), Some("completions"));
+    assert_eq!(config.arguments, vec!["zsh".to_string()]);
+
+    assert_eq!(Shell::parse("fish").unwrap(), Shell::Fish);
+    assert!(matches!(Shell::parse("tcsh"), Err(CliError::InvalidArgument(_))));
+}
+
+#[test]
+fn test_man_page_sections_follow_option_table() {
+    let page = man_page();
+
+    assert!(page.starts_with(".TH ROLO 1 "));
+    for section in [".SH NAME", ".SH SYNOPSIS", ".SH COMMANDS", ".SH OPTIONS", ".SH ENVIRONMENT", ".SH FILES", ".SH EXAMPLES"] {
+        assert!(page.contains(section), "missing {}", section);
+    }
+
+    // Options are grouped by the subcommand they affect
+    let list_section = &page[page.find(".SS list options").unwrap()..];
+    let list_section = &list_section[..list_section.find(".SH").unwrap()];
+    assert!(list_section.contains("\\fB\\-\\-list\\-style\\fR \\fISTYLE\\fR"));
+    assert!(list_section.contains("\\fB\\-\\-line\\-numbers\\fR, \\fB\\-n\\fR"));
+    assert!(page.contains(".SS columns options\n.TP\n\\fB\\-\\-cols\\fR \\fIN\\fR\n"));
+
+    for option in OPTIONS {
+        assert!(page.contains(&format!("\\fB\\-\\-{}\\fR", option.long.replace('-', "\\-"))), "--{} undocumented", option.long);
+    }
+    for (var, _) in ENV_VARS {
+        assert!(page.contains(&format!(".B {}\n", var)), "{} undocumented", var);
+    }
+    assert!(page.contains(".B ROLO_PROFILE\n"));
+    assert!(page.contains("\\fB\\-\\-output\\fR, \\fB\\-\\-follow\\fR, \\fB\\-\\-explain\\fR apply to one run"));
+}
@@ -0,0 +1,252 @@
+//! Config file and profile feature tests - defaults layered under CLI flags
+
+use rololib::config::{find_project_config, format_config_show, starter_config, write_starter_config, CONFIG_KEYS, PROJECT_CONFIG_NAME};
+use rololib::prelude::*;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::slice;
+
+fn argv(words: &[&str]) -> Vec<String> {
+    std::iter::once("rolo").chain(words.iter().copied()).map(String::from).collect()
+}
+
+/// Fresh scratch directory per test under the system temp dir
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("rolo-config-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn write_config(dir: &Path, name: &str, text: &str) -> PathBuf {
+    let path = dir.join(name);
+    fs::write(&path, text).unwrap();
+    path
+}
+
+#[test]
+fn test_config_layers_user_project_and_cli() {
+    let dir = scratch_dir("layers");
+    let user = write_config(&dir, "config.toml", "mode = \"table\"\nwidth = 100\ngap = 4\n");
+    let project = write_config(&dir, PROJECT_CONFIG_NAME, "# project defaults\nwidth = 60\n");
+
+    let resolved = resolve_config_from(&argv(&["--gap", "1"]), &[user.clone(), project.clone()]).unwrap();
+
+    assert_eq!(resolved.config.mode, CliMode::Table);
+    assert_eq!(resolved.config.width, Some(60));
+    assert_eq!(resolved.config.gap, Some(1));
+    assert_eq!(resolved.source("mode"), ValueSource::File(user));
+    assert_eq!(resolved.source("width"), ValueSource::File(project));
+    assert_eq!(resolved.source("gap"), ValueSource::Cli);
+    assert_eq!(resolved.source("cols"), ValueSource::Default);
+
+    // A subcommand picks the mode on the command line
+    let resolved = resolve_config_from(&argv(&["list"]), &resolved.files).unwrap();
+    assert_eq!(resolved.config.mode, CliMode::List);
+    assert_eq!(resolved.source("mode"), ValueSource::Cli);
+}
+
+#[test]
+fn test_config_profiles() {
+    let dir = scratch_dir("profiles");
+    let path = write_config(
+        &dir,
+        "config.toml",
+        "delimiter = \",\"\n\n[profile.wide]\nwidth = 180\nalignment = \"right\"\n\n[profile.\"tsv\"]\ndelimiter = \"\\t\"\nlist_style = 'bullets'\n",
+    );
+    let paths = [path.clone()];
+
+    let resolved = resolve_config_from(&argv(&["--profile", "tsv"]), &paths).unwrap();
+    assert_eq!(resolved.config.delimiter.as_deref(), Some("\t"));
+    assert_eq!(resolved.config.list_style.as_deref(), Some("bullets"));
+    assert_eq!(resolved.config.width, None, "other profiles don't apply");
+    assert_eq!(resolved.source("delimiter"), ValueSource::Profile("tsv".to_string(), path.clone()));
+
+    let resolved = resolve_config_from(&argv(&["--profile=wide", "--width", "90"]), &paths).unwrap();
+    assert_eq!(resolved.config.width, Some(90));
+    assert!(matches!(resolved.config.alignment, ListAlignment::Right));
+
+    let err = resolve_config_from(&argv(&["--profile", "narrow"]), &paths).unwrap_err();
+    assert!(matches!(err, CliError::Config(ConfigError::UnknownProfile(_, _))));
+    assert!(err.to_string().contains("tsv, wide"), "lists defined profiles: {}", err);
+}
+
+#[test]
+fn test_config_errors_name_file_and_line() {
+    let dir = scratch_dir("errors");
+    let bad_syntax = write_config(&dir, "syntax.toml", "mode = \"list\"\nwidth = wide\n");
+    let bad_value = write_config(&dir, "value.toml", "[profile.x]\ngap = 99\n");
+    let unknown_key = write_config(&dir, "key.toml", "colour = \"never\"\n");
+
+    let err = resolve_config_from(&argv(&[]), &[bad_syntax]).unwrap_err();
+    assert!(err.to_string().contains("syntax.toml:2:"), "{}", err);
+
+    let err = resolve_config_from(&argv(&["--profile", "x"]), &[bad_value]).unwrap_err();
+    assert!(matches!(err, CliError::Config(ConfigError::InvalidSetting(_, _, _))));
+    assert!(err.to_string().contains("Gap too large"), "{}", err);
+
+    let err = resolve_config_from(&argv(&[]), slice::from_ref(&unknown_key)).unwrap_err();
+    assert!(err.to_string().contains("unknown setting 'colour'"), "{}", err);
+
+    // Help never depends on the config files
+    assert!(resolve_config_from(&argv(&["--help"]), &[unknown_key]).unwrap().config.help);
+}
+
+fn env(vars: &[(&str, &str)]) -> Vec<(String, String)> {
+    vars.iter().map(|(var, value)| (var.to_string(), value.to_string())).collect()
+}
+
+#[test]
+fn test_env_overrides_config_and_yields_to_cli() {
+    let dir = scratch_dir("env");
+    let path = write_config(&dir, "config.toml", "width = 100\ngap = 4\ncols = 3\n\n[profile.wide]\nwidth = 180\n");
+    let paths = [path.clone()];
+    let vars = env(&[("ROLO_WIDTH", "70"), ("ROLO_GAP", "1"), ("ROLO_DELIM", "|"), ("ROLO_PRE", "sort")]);
+
+    let resolved = resolve_config_with(&argv(&["--gap", "2", "--pre", "uniq"]), &paths, &vars).unwrap();
+    assert_eq!(resolved.config.width, Some(70));
+    assert_eq!(resolved.config.gap, Some(2));
+    assert_eq!(resolved.config.columns, Some(3));
+    assert_eq!(resolved.config.delimiter.as_deref(), Some("|"));
+    assert_eq!(resolved.config.pre_commands, vec!["uniq".to_string()], "flags replace ROLO_PRE");
+    assert_eq!(resolved.source("width"), ValueSource::Env("ROLO_WIDTH".to_string()));
+    assert_eq!(resolved.source("gap"), ValueSource::Cli);
+    assert_eq!(resolved.source("cols"), ValueSource::File(path));
+
+    // ROLO_PROFILE selects a profile, and env values still beat it
+    let resolved = resolve_config_with(&argv(&[]), &paths, &env(&[("ROLO_PROFILE", "wide")])).unwrap();
+    assert_eq!(resolved.config.profile.as_deref(), Some("wide"));
+    assert_eq!(resolved.config.width, Some(180));
+    let resolved = resolve_config_with(&argv(&[]), &paths, &env(&[("ROLO_PROFILE", "wide"), ("ROLO_WIDTH", "90")])).unwrap();
+    assert_eq!(resolved.config.width, Some(90));
+}
+
+#[test]
+fn test_env_invalid_values_match_flag_errors() {
+    for (var, flag, value) in [("ROLO_WIDTH", "--width", "5"), ("ROLO_COLS", "--cols", "11"), ("ROLO_COLOR", "--color", "sometimes")] {
+        let from_env = resolve_config_with(&argv(&[]), &[], &env(&[(var, value)])).unwrap_err();
+        let from_flag = resolve_config_with(&argv(&[flag, value]), &[], &[]).unwrap_err();
+        assert_eq!(from_env.to_string(), from_flag.to_string());
+    }
+
+    // Unrelated variables are ignored
+    assert!(resolve_config_with(&argv(&[]), &[], &env(&[("ROLO_BORDERZ", "x")])).is_ok());
+}
+
+#[test]
+fn test_apply_setting_shares_cli_validation() {
+    let mut config = CliConfig::default();
+    apply_setting(&mut config, "cols", "3").unwrap();
+    apply_setting(&mut config, "line_numbers", "true").unwrap();
+
+    assert_eq!(config.columns, Some(3));
+    assert!(config.line_numbers);
+    assert!(apply_setting(&mut config, "cols", "11").is_err());
+    assert!(apply_setting(&mut config, "fit", "maybe").is_err());
+    assert!(apply_setting(&mut config, "colour", "never").is_err());
+}
+
+#[test]
+fn test_config_show_reports_values_and_sources() {
+    let dir = scratch_dir("show");
+    let path = write_config(&dir, "config.toml", "[profile.wide]\nwidth = 150\n");
+
+    let resolved = resolve_config_from(&argv(&["config", "show", "--profile", "wide", "--table"]), slice::from_ref(&path)).unwrap();
+    assert_eq!(resolved.config.subcommand.as_deref(), Some("config"));
+    assert_eq!(resolved.config.arguments, vec!["show".to_string()]);
+
+    let shown = format_config_show(&resolved);
+    assert!(shown.contains(&path.display().to_string()));
+    assert!(shown.contains("Profile: wide"));
+    let line = |key: &str| shown.lines().find(|line| line.starts_with(&format!("{} ", key))).unwrap().to_string();
+    assert!(line("width").contains("150") && line("width").contains("profile 'wide'"), "{}", shown);
+    assert!(line("mode").contains("table") && line("mode").ends_with("command line"), "{}", shown);
+    assert!(line("gap").ends_with("default"), "{}", shown);
+}
+
+#[test]
+fn test_find_project_config_searches_parents() {
+    let dir = scratch_dir("project");
+    let nested = dir.join("a").join("b");
+    fs::create_dir_all(&nested).unwrap();
+    let path = dir.join(PROJECT_CONFIG_NAME);
+    assert_ne!(find_project_config(&nested), Some(path.clone()));
+
+    fs::write(&path, "gap = 3\n").unwrap();
+    assert_eq!(find_project_config(&nested), Some(path));
+}
+
+#[test]
+fn test_starter_config_documents_every_setting() {
+    let dir = scratch_dir("starter");
+    let text = starter_config();
+
+    // As written, everything is commented out
+    let path = write_config(&dir, "config.toml", &text);
+    let resolved = resolve_config_from(&argv(&[]), slice::from_ref(&path)).unwrap();
+    assert!(CONFIG_KEYS.iter().all(|key| resolved.source(key) == ValueSource::Default));
+
+    // Every example value is valid once uncommented
+    let top_level = text.split("# Named profiles").next().unwrap();
+    let uncommented: String = top_level
+        .lines()
+        .map(|line| match line.strip_prefix("# ") {
+            Some(setting) if setting.contains(" = ") => setting,
+            _ => line,
+        })
+        .map(|line| format!("{}\n", line))
+        .collect();
+    let path = write_config(&dir, "uncommented.toml", &uncommented);
+    let resolved = resolve_config_from(&argv(&[]), slice::from_ref(&path)).unwrap();
+    for key in CONFIG_KEYS {
+        assert_eq!(resolved.source(key), ValueSource::File(path.clone()), "{} missing from starter config", key);
+    }
+}
+
+#[test]
+fn test_write_starter_config_refuses_to_overwrite() {
+    let dir = scratch_dir("init");
+    let path = dir.join("rolo").join("config.toml");
+
+    write_starter_config(&path, false).unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap(), starter_config());
+
+    fs::write(&path, "gap = 5\n").unwrap();
+    let err = write_starter_config(&path, false).unwrap_err();
+    assert!(matches!(err, ConfigError::AlreadyExists(_)));
+    assert_eq!(CliError::from(err).hint().as_deref(), Some("Pass --force to overwrite it"));
+    assert_eq!(fs::read_to_string(&path).unwrap(), "gap = 5\n");
+
+    write_starter_config(&path, true).unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap(), starter_config());
+
+    let config = parse_args(&argv(&["init", "--force"])).unwrap();
+    assert_eq!(config.subcommand.as_deref(), Some("init"));
+    assert!(config.force);
+}
+
+#[test]
+fn test_flatten_settings_from_config_and_env() {
+    let dir = scratch_dir("flatten");
+    let path = write_config(&dir, "config.toml", "max_depth = 1\narrays = \"index\"\nfields = \"name, user\"\n");
+    let paths = [path.clone()];
+
+    let resolved = resolve_config_with(&argv(&["table"]), &paths, &[]).unwrap();
+    assert_eq!(resolved.config.flatten, FlattenConfig { max_depth: Some(1), arrays: ArrayStyle::Index });
+    assert_eq!(resolved.config.fields, vec!["name", "user"]);
+    assert_eq!(resolved.source("fields"), ValueSource::File(path));
+
+    let vars = env(&[("ROLO_MAX_DEPTH", "3"), ("ROLO_ARRAYS", "join"), ("ROLO_FIELDS", "id")]);
+    let resolved = resolve_config_with(&argv(&["table", "--arrays", "json"]), &paths, &vars).unwrap();
+    assert_eq!(resolved.config.flatten, FlattenConfig { max_depth: Some(3), arrays: ArrayStyle::Json });
+    assert_eq!(resolved.config.fields, vec!["id"]);
+    assert_eq!(resolved.source("max_depth"), ValueSource::Env("ROLO_MAX_DEPTH".to_string()));
+    assert_eq!(resolved.source("arrays"), ValueSource::Cli);
+
+    let shown = format_config_show(&resolve_config_with(&argv(&[]), &[], &[]).unwrap());
+    assert!(shown.lines().any(|line| line.starts_with("max_depth ") && line.contains("all")), "{}", shown);
+    assert!(shown.lines().any(|line| line.starts_with("arrays ") && line.contains("json")), "{}", shown);
+
+    let err = resolve_config_with(&argv(&[]), &[], &env(&[("ROLO_ARRAYS", "split")])).unwrap_err();
+    assert_eq!(err.to_string(), resolve_config_with(&argv(&["--arrays", "split"]), &[], &[]).unwrap_err().to_string());
+}
@@ -0,0 +1,97 @@
+//! Error reporting feature tests - exit codes per error class and JSON reports
+
+use rololib::cli::error::{EXIT_CONFIG, EXIT_IO, EXIT_LAYOUT, EXIT_USAGE};
+use rololib::cli::{format_error, requested_error_format};
+use rololib::prelude::*;
+
+fn argv(words: &[&str]) -> Vec<String> {
+    std::iter::once("rolo").chain(words.iter().copied()).map(String::from).collect()
+}
+
+#[test]
+fn test_error_kinds_have_distinct_exit_codes() {
+    let cases = [
+        (CliError::InvalidArgument("--cols".to_string()), ErrorKind::Usage, EXIT_USAGE),
+        (CliError::Layout(LayoutError::WidthTooSmall(20, 36)), ErrorKind::Layout, EXIT_LAYOUT),
+        (CliError::Stream(StreamError::BinaryInput("NUL bytes".to_string())), ErrorKind::Io, EXIT_IO),
+        (CliError::Config(ConfigError::UnknownProfile("x".to_string(), Vec::new())), ErrorKind::Config, EXIT_CONFIG),
+        (CliError::Stream(StreamError::PipeBroken("EPIPE".to_string())), ErrorKind::BrokenPipe, BROKEN_PIPE_EXIT_CODE),
+    ];
+
+    for (error, kind, code) in cases {
+        assert_eq!(error.kind(), kind, "{}", error);
+        assert_eq!(error.exit_code(), code, "{}", error);
+    }
+
+    let mut codes: Vec<i32> = [EXIT_USAGE, EXIT_LAYOUT, EXIT_IO, EXIT_CONFIG, BROKEN_PIPE_EXIT_CODE, 0, 1].to_vec();
+    codes.sort();
+    codes.dedup();
+    assert_eq!(codes.len(), 7);
+}
+
+#[test]
+fn test_run_cli_exit_codes() {
+    assert_eq!(run_cli(&argv(&["--cols", "abc"])), EXIT_USAGE);
+    assert_eq!(run_cli(&argv(&["frobnicate", "--error-format", "json"])), EXIT_USAGE);
+    assert_eq!(run_cli(&argv(&["completions", "tcsh"])), EXIT_USAGE);
+    assert_eq!(run_cli(&argv(&["--error-format", "xml"])), EXIT_USAGE);
+}
+
+#[test]
+fn test_error_format_json_report() {
+    let error = CliError::Layout(LayoutError::WidthTooSmall(20, 36));
+    let json = format_error(&error, Some("Columns"), ErrorFormat::Json);
+    assert_eq!(
+        json,
+        "{\"kind\":\"layout\",\"message\":\"Width 20 is too small for gaps requiring 36 spaces\",\"hint\":\"Use a larger --width, fewer --cols or a smaller --gap\"}"
+    );
+
+    // Messages are escaped, and a missing hint is null
+    let error = CliError::Stream(StreamError::CommandFailed("grep \"x\"".to_string(), 2));
+    let json = format_error(&error, None, ErrorFormat::Json);
+    assert_eq!(json, "{\"kind\":\"io\",\"message\":\"Command 'grep \\\"x\\\"' failed with exit code 2\",\"hint\":null}");
+
+    // Text reports keep the message first and the hint on the next line
+    let text = format_error(&CliError::UnsupportedCommand("frobnicate".to_string()), None, ErrorFormat::Text);
+    assert_eq!(text, "❌ Unsupported command: frobnicate\nUse 'rolo help' for usage information");
+}
+
+#[test]
+fn test_error_format_option() {
+    assert_eq!(parse_args(&argv(&[])).unwrap().error_format, ErrorFormat::Text);
+    assert_eq!(parse_args(&argv(&["--error-format", "json"])).unwrap().error_format, ErrorFormat::Json);
+
+    // Found even when the rest of the arguments don't parse
+    assert_eq!(requested_error_format(&argv(&["--cols", "abc", "--error-format=json"])), ErrorFormat::Json);
+    assert_eq!(requested_error_format(&argv(&["--error-format", "json", "--error-format", "text"])), ErrorFormat::Text);
+    assert_eq!(requested_error_format(&argv(&["exec", "--", "tool", "--error-format", "json"])), ErrorFormat::Text);
+}
+
+#[test]
+fn test_unfit_columns_suggest_options_from_input() {
+    let config = parse_args(&argv(&["--cols", "3", "--gap", "20", "--width", "40"])).unwrap();
+    let err = rololib::cli::render_layout(&config, "alpha\nbeta\ngamma\n").unwrap_err();
+
+    assert!(matches!(err, CliError::Unfit(_, _)));
+    assert_eq!(err.kind(), ErrorKind::Layout);
+    assert_eq!(err.to_string(), "3 columns need at least 49 cells at --width 40 with --gap 20");
+    assert_eq!(err.hint().as_deref(), Some("Try --cols 2, --gap 15 or --width 55"));
+
+    // Never more columns than items, and no width beyond the maximum
+    let config = parse_args(&argv(&["--cols", "10", "--gap", "20", "--width", "40"])).unwrap();
+    let err = rololib::cli::render_layout(&config, "a\nb\n").unwrap_err();
+    assert_eq!(err.hint().as_deref(), Some("Try --cols 2 or --gap 1"));
+}
+
+#[test]
+fn test_guess_delimiter() {
+    use rololib::layout::guess_delimiter;
+
+    assert_eq!(guess_delimiter(&std::fs::read_to_string("tests/data/sample.csv").unwrap()), Some(","));
+    assert_eq!(guess_delimiter(&std::fs::read_to_string("tests/data/sample.tsv").unwrap()), Some("\t"));
+    assert_eq!(guess_delimiter("a|b|c\nd|e|f\n"), Some("|"));
+    assert_eq!(guess_delimiter("plain\nwords\n"), None);
+
+    // A consistent split beats one that only sometimes appears more often
+    assert_eq!(guess_delimiter("name;note\nx;a, b, c\ny;d\n"), Some(";"));
+}
@@ -0,0 +1,127 @@
+//! `--explain` feature tests - table sizing, scaling and truncation reports
+
+use rololib::cli::{explain_layout, render_layout, ExplainFormat};
+use rololib::layout::{plan_table, TruncatedCell};
+use rololib::prelude::*;
+
+fn argv(words: &[&str]) -> Vec<String> {
+    std::iter::once("rolo").chain(words.iter().copied()).map(String::from).collect()
+}
+
+const SQUASHED: &str = "name\tdescription\nalpha\tA fairly long description that will not fit\nbeta\tshort\n";
+
+#[test]
+fn test_plan_table_matches_rendered_widths() {
+    let model = TableModel::parse(SQUASHED, "\t");
+    let plan = plan_table(&model, 30);
+
+    assert_eq!(plan.natural_widths, vec![5, 43]);
+    assert_eq!(plan.required_width, 51);
+    assert!(plan.scale_factor.is_some());
+    assert_eq!(plan.widths, vec![3, 24]);
+    assert_eq!(plan.truncated, vec![
+        TruncatedCell { row: 0, column: 0, width: 4 },
+        TruncatedCell { row: 1, column: 0, width: 5 },
+        TruncatedCell { row: 1, column: 1, width: 43 },
+        TruncatedCell { row: 2, column: 0, width: 4 },
+    ]);
+
+    // The header separator is drawn at exactly the planned widths
+    let table = format_table_model(&model, 30).unwrap();
+    assert_eq!(table.lines().nth(1).unwrap(), "----+-------------------------");
+
+    // A table that fits is left alone
+    let plan = plan_table(&model, 80);
+    assert_eq!(plan.widths, plan.natural_widths);
+    assert_eq!(plan.scale_factor, None);
+    assert!(plan.truncated.is_empty());
+}
+
+#[test]
+fn test_explain_option_parsing() {
+    assert_eq!(parse_args(&argv(&["table"])).unwrap().explain, None);
+    assert_eq!(parse_args(&argv(&["table", "--explain"])).unwrap().explain, Some(ExplainFormat::Text));
+    assert_eq!(parse_args(&argv(&["--explain", "json", "table"])).unwrap().explain, Some(ExplainFormat::Json));
+    assert_eq!(parse_args(&argv(&["--explain=text"])).unwrap().explain, Some(ExplainFormat::Text));
+
+    // Only a format name is taken from the next argument
+    let config = parse_args(&argv(&["--explain", "table"])).unwrap();
+    assert_eq!(config.explain, Some(ExplainFormat::Text));
+    assert_eq!(config.mode, CliMode::Table);
+
+    let err = parse_args(&argv(&["--explain=yaml"])).unwrap_err();
+    assert!(err.to_string().contains("text, json"), "{}", err);
+}
+
+#[test]
+fn test_explain_reports_table_scaling_and_truncation() {
+    let config = parse_args(&argv(&["table", "--width", "30", "--explain"])).unwrap();
+    let explanation = explain_layout(&config, SQUASHED);
+
+    assert_eq!(explanation.width, 30);
+    assert_eq!(explanation.delimiter.as_deref(), Some("\t"));
+    assert_eq!(explanation.delimiter_source, "default");
+    assert_eq!((explanation.rows, explanation.columns), (2, 2));
+    assert!(explanation.header);
+
+    let text = explanation.to_text();
+    assert!(text.contains("Width: 30 (--width)"), "{}", text);
+    assert!(text.contains("Rows: 2 plus header"), "{}", text);
+    assert!(text.contains("Required width: 51"), "{}", text);
+    assert!(text.contains("Scaling: columns scaled by 0.56 to fit 30"), "{}", text);
+    assert!(text.contains("       2       43     24  (-19)"), "{}", text);
+    assert!(text.contains("row 2, column 2: 43 -> 24"), "{}", text);
+
+    let json = explanation.render(ExplainFormat::Json);
+    assert!(json.starts_with("{\"mode\":\"table\",\"width\":30,"), "{}", json);
+    assert!(json.contains("\"delimiter\":\"\\t\""), "{}", json);
+    assert!(json.contains("{\"column\":2,\"natural\":43,\"final\":24}"), "{}", json);
+    assert!(json.contains("{\"row\":2,\"header\":false,\"column\":2,\"width\":43,\"final\":24}"), "{}", json);
+}
+
+#[test]
+fn test_explain_names_the_width_source() {
+    let source = |args: &[&str], vars: &[(&str, &str)], paths: &[std::path::PathBuf]| {
+        let vars: Vec<(String, String)> = vars.iter().map(|(var, value)| (var.to_string(), value.to_string())).collect();
+        let resolved = resolve_config_with(&argv(args), paths, &vars).unwrap();
+        explain_layout(&resolved.config, "a\tb\n").width_source
+    };
+
+    let dir = std::env::temp_dir().join(format!("rolo-explain-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("config.toml");
+    std::fs::write(&path, "width = 50\n").unwrap();
+    let paths = [path.clone()];
+
+    assert_eq!(source(&["table"], &[], &paths), path.display().to_string());
+    assert_eq!(source(&["table"], &[("ROLO_WIDTH", "60")], &paths), "env ROLO_WIDTH");
+    assert_eq!(source(&["table", "--width", "70"], &[("ROLO_WIDTH", "60")], &paths), "--width");
+    assert!(source(&["table", "--no-fit"], &[], &[]).starts_with("fixed"));
+}
+
+#[test]
+fn test_explain_columns_reports_problem() {
+    let config = parse_args(&argv(&["--cols", "10", "--gap", "20", "--width", "60", "--no-fit"])).unwrap();
+    let explanation = explain_layout(&config, "a\nb\nc\n");
+
+    assert_eq!((explanation.items, explanation.rows, explanation.columns), (3, 1, 10));
+    assert_eq!(explanation.column_width, None);
+    assert!(explanation.problem.unwrap().ends_with("Try --cols 3 or --gap 3"));
+
+    let config = parse_args(&argv(&["--cols", "2", "--width", "42", "--delim", ","])).unwrap();
+    let explanation = explain_layout(&config, "a,b,c\n");
+    assert_eq!(explanation.column_width, Some(20));
+    assert_eq!(explanation.delimiter_source, "--delim");
+    assert!(explanation.to_json().contains("\"items\":3,\"column_width\":20"));
+}
+
+#[test]
+fn test_explain_leaves_output_alone() {
+    let plain = parse_args(&argv(&["table", "--width", "30"])).unwrap();
+    let explained = parse_args(&argv(&["table", "--width", "30", "--explain=json"])).unwrap();
+    assert_eq!(render_layout(&explained, SQUASHED).unwrap(), render_layout(&plain, SQUASHED).unwrap());
+
+    let follow = parse_args(&argv(&["table", "--follow", "--explain"])).unwrap();
+    let err = rololib::cli::execute_cli(&follow).unwrap_err();
+    assert!(err.to_string().contains("--explain can't be used with --follow"), "{}", err);
+}
@@ -0,0 +1,87 @@
+//! Input format feature tests - `--format` selection and builds without format support
+
+use rololib::cli::{explain_layout, render_layout};
+use rololib::prelude::*;
+
+fn argv(words: &[&str]) -> Vec<String> {
+    std::iter::once("rolo").chain(words.iter().copied()).map(String::from).collect()
+}
+
+#[test]
+fn test_format_option_is_parsed() {
+    assert_eq!(parse_args(&argv(&["table"])).unwrap().input_format, InputFormat::Auto);
+    assert_eq!(parse_args(&argv(&["table", "--format", "csv"])).unwrap().input_format, InputFormat::Csv);
+    assert_eq!(parse_args(&argv(&["table", "--format=delimited"])).unwrap().input_format, InputFormat::Delimited);
+
+    let err = parse_args(&argv(&["table", "--format", "xml"])).unwrap_err();
+    assert!(err.to_string().contains("use auto, delimited, csv, json or jsonl"), "{}", err);
+}
+
+#[test]
+fn test_delimited_format_splits_lines() {
+    let model = parse_table("a,b\n\"x,y\",z\n", InputFormat::Delimited, ",").unwrap();
+    assert_eq!(model.rows, vec![vec!["\"x", "y\"", "z"]]);
+
+    // Tab-delimited input is never parsed as CSV
+    assert_eq!(InputFormat::Auto.resolve("\t"), InputFormat::Delimited);
+}
+
+#[cfg(not(feature = "csv-support"))]
+#[test]
+fn test_csv_needs_feature() {
+    // `auto` falls back to line splitting, an explicit --format csv is an error
+    assert_eq!(InputFormat::Auto.resolve(","), InputFormat::Delimited);
+
+    let config = parse_args(&argv(&["table", "--format", "csv"])).unwrap();
+    let err = render_layout(&config, "a,b\n1,2\n").unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Usage);
+    assert!(err.to_string().contains("'csv-support' feature"), "{}", err);
+    assert!(err.hint().unwrap().contains("--format delimited"));
+}
+
+#[test]
+fn test_explain_reports_format() {
+    let config = parse_args(&argv(&["table", "--format", "delimited", "--delim", ","])).unwrap();
+    let explanation = explain_layout(&config, "a,b\n1,2\n");
+    assert_eq!(explanation.format, Some("delimited"));
+    assert!(explanation.to_text().contains("Format: delimited"));
+    assert!(explanation.to_json().contains("\"format\":\"delimited\""));
+}
+
+#[cfg(not(feature = "json-support"))]
+#[test]
+fn test_json_needs_feature() {
+    for format in ["json", "jsonl"] {
+        let config = parse_args(&argv(&["table", "--format", format])).unwrap();
+        let err = render_layout(&config, "{\"a\": 1}\n").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Usage);
+        assert!(err.to_string().contains("'json-support' feature"), "{}", err);
+    }
+}
+
+#[test]
+fn test_flatten_options_are_parsed() {
+    let config = parse_args(&argv(&["table", "--max-depth", "2", "--arrays", "join", "--fields", "a, b.c,"])).unwrap();
+    assert_eq!(config.flatten, FlattenConfig { max_depth: Some(2), arrays: ArrayStyle::Join });
+    assert_eq!(config.fields, vec!["a", "b.c"]);
+
+    assert_eq!(parse_args(&argv(&["table"])).unwrap().flatten, FlattenConfig::default());
+    assert!(parse_args(&argv(&["table", "--arrays", "split"])).is_err());
+    assert!(parse_args(&argv(&["table", "--max-depth", "-1"])).is_err());
+    assert!(parse_args(&argv(&["table", "--fields", ","])).is_err());
+}
+
+#[test]
+fn test_fields_select_delimited_columns() {
+    let model = parse_table("name\tage\tcity\nAnn\t30\tOslo\nBen\t25", InputFormat::Delimited, "\t").unwrap();
+
+    let selected = select_fields(&model, &["city", "name"]).unwrap();
+    assert_eq!(selected.headers, Some(vec!["city".to_string(), "name".to_string()]));
+    assert_eq!(selected.rows, vec![vec!["Oslo", "Ann"], vec!["", "Ben"]]);
+
+    let err = select_fields(&model, &["town"]).unwrap_err();
+    assert_eq!(err.to_string(), "No column matches field 'town' (columns: name, age, city)");
+
+    let config = parse_args(&argv(&["table", "--fields", "age", "--width", "40"])).unwrap();
+    assert_eq!(render_layout(&config, "name\tage\nAnn\t30\n").unwrap(), "age\n---\n30");
+}
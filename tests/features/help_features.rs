@@ -0,0 +1,130 @@
+//! Per-command help feature tests - relevant options, limits, defaults and runnable examples
+
+use rololib::cli::options::{OptionAction, COMMANDS, COMMAND_NAMES, MODES, OPTIONS};
+use rololib::cli::{command_help_text, render_layout};
+use rololib::prelude::*;
+use std::path::Path;
+
+fn argv(words: &[&str]) -> Vec<String> {
+    std::iter::once("rolo").chain(words.iter().copied()).map(String::from).collect()
+}
+
+#[test]
+fn test_command_help_shows_only_relevant_options() {
+    let table = command_help_text("table").unwrap();
+    assert!(table.starts_with("rolo table - "));
+    assert!(table.contains("--width=N             Terminal width (10-200; default terminal width, or 80 with --no-fit)"));
+    assert!(table.contains("--delim=STR"));
+    assert!(!table.contains("--cols"), "columns-only option in table help");
+    assert!(!table.contains("--list-style"), "list-only option in table help");
+    assert!(table.contains("rolo table < tests/data/sample.tsv"));
+
+    let columns = command_help_text("columns").unwrap();
+    assert!(columns.contains("--cols=N              Number of columns (1-10; default 2)"));
+    assert!(columns.contains("--gap=N               Gap between columns (0-20; default 2)"));
+
+    let list = command_help_text("list").unwrap();
+    assert!(list.contains("--list-style=STYLE    List style (numbers, bullets, stars, dots, dash)"));
+    assert!(list.contains("--line-numbers, -n"));
+    assert!(!list.contains("--gap"));
+
+    let watch = command_help_text("watch").unwrap();
+    assert!(watch.contains("--interval=TIME"));
+    assert!(watch.contains("rolo help MODE"));
+
+    assert!(command_help_text("tabel").is_none());
+}
+
+#[test]
+fn test_help_requests_are_parsed_per_command() {
+    let config = parse_args(&argv(&["table", "--help"])).unwrap();
+    assert!(config.help);
+    assert_eq!(config.subcommand.as_deref(), Some("table"));
+
+    let config = parse_args(&argv(&["help", "list"])).unwrap();
+    assert_eq!(config.subcommand.as_deref(), Some("help"));
+    assert_eq!(config.arguments, vec!["list".to_string()]);
+}
+
+#[test]
+fn test_command_names_match_command_table() {
+    let names: Vec<&str> = COMMANDS.iter().map(|command| command.name).collect();
+    assert_eq!(names, COMMAND_NAMES);
+}
+
+#[test]
+fn test_documented_ranges_match_validation() {
+    for option in OPTIONS {
+        if let (Some((min, max)), OptionAction::Set(key)) = (option.range, option.action) {
+            let accepts = |value: usize| apply_setting(&mut CliConfig::default(), key, &value.to_string()).is_ok();
+            assert!(accepts(min) && accepts(max), "--{} rejects its documented range", option.long);
+            assert!(!accepts(max + 1), "--{} accepts more than {}", option.long, max);
+            if min > 0 {
+                assert!(!accepts(min - 1), "--{} accepts less than {}", option.long, min);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_layout_examples_run_against_test_data() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+
+    for command in COMMANDS.iter().filter(|command| MODES.contains(&command.name)) {
+        for (_, example) in command.examples {
+            let (words, file) = example.split_once(" < ").expect("layout examples read a file");
+            let words: Vec<&str> = words.split_whitespace().skip(1).collect();
+
+            let config = parse_args(&argv(&words)).unwrap_or_else(|e| panic!("{}: {}", example, e));
+            let input = std::fs::read_to_string(root.join(file)).unwrap();
+            let output = render_layout(&config, &input).unwrap_or_else(|e| panic!("{}: {}", example, e));
+            assert!(!output.trim().is_empty(), "{} printed nothing", example);
+        }
+    }
+}
+
+#[test]
+fn test_features_constant_matches_build() {
+    for (name, enabled) in [
+        ("libc", cfg!(feature = "libc")),
+        ("width-boxy", cfg!(feature = "width-boxy")),
+        ("csv-support", cfg!(feature = "csv-support")),
+        ("json-support", cfg!(feature = "json-support")),
+        ("markdown", cfg!(feature = "markdown")),
+        ("tokens", cfg!(feature = "tokens")),
+        ("themes", cfg!(feature = "themes")),
+    ] {
+        assert_eq!(FEATURES.contains(&name), enabled, "{}", name);
+    }
+    assert_eq!(WIDTH_STRATEGY.starts_with("boxy"), cfg!(feature = "width-boxy"));
+}
+
+#[test]
+fn test_version_verbose_flag() {
+    for words in [&["version", "--verbose"][..], &["--version", "-v"], &["-v", "--version"]] {
+        let config = parse_args(&argv(words)).unwrap();
+        assert!(config.verbose, "{:?}", words);
+    }
+    assert!(!parse_args(&argv(&["version"])).unwrap().verbose);
+    assert!(!parse_args(&argv(&["--version", "--", "--verbose"])).unwrap().verbose);
+
+    let help = command_help_text("version").unwrap();
+    assert!(help.contains("--verbose, -v"), "{}", help);
+}
+
+#[test]
+fn test_help_descriptions_start_after_the_longest_label() {
+    let width = OPTIONS.iter().map(|option| option.label().chars().count()).max().unwrap();
+    let mut checked = 0;
+    for command in COMMANDS {
+        let help = command_help_text(command.name).unwrap();
+        for option in OPTIONS {
+            let prefix = format!("    {} ", option.label());
+            if let Some(line) = help.lines().find(|line| line.starts_with(&prefix)) {
+                assert_eq!(line, format!("    {:<width$} {}", option.label(), option.summary()), "in '{}' help", command.name);
+                checked += 1;
+            }
+        }
+    }
+    assert!(checked > 0);
+}
@@ -0,0 +1,57 @@
+//! Binary input detection and control byte escaping feature tests
+
+use rololib::prelude::*;
+
+const ELF_HEADER: &[u8] = b"\x7fELF\x02\x01\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00\x03\x00>\x00";
+
+#[test]
+fn test_binary_input_is_refused_by_default() {
+    let result = check_binary(ELF_HEADER, &StreamConfig::default());
+    match result {
+        Err(StreamError::BinaryInput(reason)) => assert!(reason.contains("NUL byte")),
+        other => panic!("expected BinaryInput, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_text_and_null_data_pass_the_check() {
+    let config = StreamConfig::default();
+    assert!(check_binary("name\tage\nalice\t30\n".as_bytes(), &config).is_ok());
+    assert!(check_binary("héllo wörld ✓\n".as_bytes(), &config).is_ok());
+
+    let null_data = StreamConfig { null_data: true, ..StreamConfig::default() };
+    assert!(check_binary(b"a\0b\0", &null_data).is_ok());
+}
+
+#[test]
+fn test_binary_ok_overrides_refusal() {
+    let config = StreamConfig { allow_binary: true, ..StreamConfig::default() };
+    assert!(check_binary(ELF_HEADER, &config).is_ok());
+}
+
+#[test]
+fn test_escape_control_chars() {
+    assert_eq!(escape_control_chars("a\0b\x07c\x7f"), "a^@b^Gc^?");
+    assert_eq!(escape_control_chars("tab\there\nnext"), "tab\there\nnext");
+    assert_eq!(escape_control_chars("\u{85}"), "\\x85");
+}
+
+#[test]
+fn test_escape_control_chars_keeps_sgr_colors() {
+    assert_eq!(escape_control_chars("\x1b[1;31mred\x1b[0m"), "\x1b[1;31mred\x1b[0m");
+    // Cursor movement and other escapes are made visible
+    assert_eq!(escape_control_chars("\x1b[2Jx"), "^[[2Jx");
+}
+
+#[test]
+fn test_binary_ok_keeps_crlf_invisible() {
+    // CRLF is normalized before control bytes are escaped, so no `^M` shows up
+    let config = StreamConfig { allow_binary: true, ..StreamConfig::default() };
+    assert_eq!(prepare_text("a\tb\r\nc\td\x07\r\n", &config), "a\tb\nc\td^G\n");
+
+    let table = format_table(&prepare_text("a\tb\r\nc\td\r\n", &config), "\t").unwrap();
+    assert!(!table.contains("^M"), "{}", table);
+
+    // A stray CR inside a line is still made visible
+    assert_eq!(prepare_text("a\rb\r\nc\r\n", &config), "a^Mb\nc\n");
+}
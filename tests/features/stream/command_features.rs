@@ -0,0 +1,145 @@
+//! Stream watch/exec mode, command execution and filter stage feature tests
+
+use rololib::cli::execute_exec;
+use rololib::prelude::*;
+use rololib::stream::highlight_changes;
+use std::time::Duration;
+
+#[test]
+fn test_parse_interval_units() {
+    assert_eq!(parse_interval("2"), Some(Duration::from_secs(2)));
+    assert_eq!(parse_interval("2s"), Some(Duration::from_secs(2)));
+    assert_eq!(parse_interval("500ms"), Some(Duration::from_millis(500)));
+    assert_eq!(parse_interval("1.5s"), Some(Duration::from_millis(1500)));
+    assert_eq!(parse_interval("1m"), Some(Duration::from_secs(60)));
+
+    assert_eq!(parse_interval("0"), None);
+    assert_eq!(parse_interval("-1s"), None);
+    assert_eq!(parse_interval("soon"), None);
+}
+
+#[test]
+fn test_highlight_changes_marks_only_changed_table_cells() {
+    let previous = "name  | count\nalpha | 1\nbeta  | 2";
+    let current = "name  | count\nalpha | 1\nbeta  | 3";
+
+    let output = highlight_changes(previous, current, Some(" | "));
+    let lines: Vec<&str> = output.lines().collect();
+
+    assert_eq!(lines[0], "name  | count");
+    assert_eq!(lines[1], "alpha | 1");
+    assert_eq!(lines[2], "beta  | \x1b[7m3\x1b[27m");
+}
+
+#[test]
+fn test_highlight_changes_by_word_and_new_lines() {
+    let output = highlight_changes("up 3 days", "up 4 days\nnew line", None);
+    let lines: Vec<&str> = output.lines().collect();
+
+    assert_eq!(lines[0], "up \x1b[7m4\x1b[27m days");
+    assert_eq!(lines[1], "\x1b[7mnew\x1b[27m \x1b[7mline\x1b[27m");
+}
+
+#[test]
+fn test_watch_config_defaults() {
+    let config = WatchConfig::new("date");
+    assert_eq!(config.command, "date");
+    assert_eq!(config.interval, Duration::from_secs(2));
+    assert!(config.cell_separator.is_none());
+}
+
+#[test]
+fn test_run_command_captures_output_and_exit_code() {
+    let output = run_command("printf 'a\\tb\\n'; echo oops >&2; exit 3").unwrap();
+    assert_eq!(output.stdout, "a\tb\n");
+    assert_eq!(output.stderr.trim(), "oops");
+    assert_eq!(output.exit_code, 3);
+    assert!(!output.success());
+
+    let quoted = shell_join(&["printf".to_string(), "%s|".to_string(), "two words".to_string()]);
+    assert_eq!(run_command(&quoted).unwrap().stdout, "two words|");
+}
+
+#[test]
+fn test_exec_command_quotes_argv_and_propagates_exit_code() {
+    let argv = vec![
+        "sh".to_string(),
+        "-c".to_string(),
+        "printf '%s\\n' \"$1\"; exit 5".to_string(),
+        "sh".to_string(),
+        "it's quoted; not split".to_string(),
+    ];
+
+    let output = exec_command(&argv).unwrap();
+    assert_eq!(output.stdout, "it's quoted; not split\n");
+    assert!(output.stderr.is_empty(), "stderr is forwarded, not captured");
+    assert_eq!(output.exit_code, 5);
+}
+
+#[test]
+fn test_exec_command_requires_argv() {
+    assert!(matches!(exec_command(&[]), Err(StreamError::IoError(_))));
+}
+
+#[test]
+fn test_exec_output_is_checked_and_decoded_like_stdin() {
+    let exec = |script: &str, options: &[&str]| {
+        let output = std::env::temp_dir().join(format!("rolo-exec-{}-{}.txt", std::process::id(), options.len()));
+        let output_arg = output.display().to_string();
+        let mut words = vec!["rolo", "exec", "list", "--output", output_arg.as_str()];
+        words.extend_from_slice(options);
+        words.extend(["--", "sh", "-c", script]);
+        let args: Vec<String> = words.iter().map(|word| word.to_string()).collect();
+        let result = execute_exec(&parse_args(&args).unwrap());
+        result.map(|code| (code, std::fs::read_to_string(&output).unwrap_or_default()))
+    };
+
+    let err = exec("printf 'a\\000\\001\\002b\\n'", &[]).unwrap_err();
+    assert!(err.to_string().contains("binary"), "{}", err);
+    assert!(exec("printf 'a\\000\\001\\002b\\n'", &["--binary-ok"]).is_ok());
+
+    assert!(exec("printf 'caf\\351\\n'", &["--encoding", "utf8"]).is_err());
+    let (code, text) = exec("printf 'caf\\351\\r\\n'; exit 3", &["--encoding", "latin1"]).unwrap();
+    assert_eq!(code, 3);
+    assert!(text.contains("café") && !text.contains('\r'), "{:?}", text);
+}
+
+#[test]
+fn test_filter_through_commands_chains_in_order() {
+    let commands = vec!["tr a-z A-Z".to_string(), "sed 's/^/> /'".to_string()];
+    let output = filter_through_commands(&commands, "one\ntwo\n".to_string()).unwrap();
+    assert_eq!(output, "> ONE\n> TWO\n");
+
+    // No stages leaves content untouched
+    assert_eq!(filter_through_commands(&[], "same".to_string()).unwrap(), "same");
+}
+
+#[test]
+fn test_filter_stage_failure_is_stream_error() {
+    let result = filter_through_command("cat >/dev/null; exit 7", "data\n");
+    match result {
+        Err(StreamError::CommandFailed(cmd, code)) => {
+            assert!(cmd.contains("exit 7"));
+            assert_eq!(code, 7);
+        }
+        other => panic!("expected CommandFailed, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_pipeline_pre_and_post_stages() {
+    let pipeline = Pipeline::new()
+        .pre("sort")
+        .post("sed 's/^/- /'");
+
+    let output = pipeline
+        .process("b\na\n", |stream| stream.map(|line| line.to_uppercase()))
+        .unwrap();
+    assert_eq!(output.trim_end(), "- A\n- B");
+
+    let failing = Pipeline::new().pre("exit 2");
+    assert!(matches!(
+        failing.process("x", |stream| stream),
+        Err(StreamError::CommandFailed(_, 2))
+    ));
+}
@@ -0,0 +1,38 @@
+//! Input decoding feature tests (strict UTF-8, lossy and Latin-1)
+
+use rololib::prelude::*;
+
+#[test]
+fn test_input_encoding_parse() {
+    assert_eq!(InputEncoding::parse("utf8"), Some(InputEncoding::Utf8));
+    assert_eq!(InputEncoding::parse("UTF-8"), Some(InputEncoding::Utf8));
+    assert_eq!(InputEncoding::parse("lossy"), Some(InputEncoding::Lossy));
+    assert_eq!(InputEncoding::parse("latin1"), Some(InputEncoding::Latin1));
+    assert_eq!(InputEncoding::parse("ebcdic"), None);
+}
+
+#[test]
+fn test_strict_utf8_rejects_invalid_bytes() {
+    let result = decode_input(b"caf\xe9\n".to_vec(), InputEncoding::Utf8);
+    assert!(matches!(result, Err(StreamError::InvalidUtf8(_))));
+}
+
+#[test]
+fn test_lossy_counts_replaced_bytes() {
+    let decoded = decode_input(b"caf\xe9 ok\n\xff\xfe\n".to_vec(), InputEncoding::Lossy).unwrap();
+    assert_eq!(decoded.text, "caf\u{FFFD} ok\n\u{FFFD}\u{FFFD}\n");
+    assert_eq!(decoded.replaced_bytes, 3);
+
+    let clean = decode_input("héllo".as_bytes().to_vec(), InputEncoding::Lossy).unwrap();
+    assert_eq!(clean.replaced_bytes, 0);
+}
+
+#[test]
+fn test_latin1_maps_every_byte() {
+    let decoded = decode_input(b"caf\xe9\tna\xefve\n".to_vec(), InputEncoding::Latin1).unwrap();
+    assert_eq!(decoded.text, "café\tnaïve\n");
+    assert_eq!(decoded.replaced_bytes, 0);
+
+    let table = format_table_with_config(&decoded.text, "\t", 80).unwrap();
+    assert!(table.contains("café"));
+}
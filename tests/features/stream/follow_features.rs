@@ -0,0 +1,91 @@
+//! Stream follow mode feature tests
+
+use rololib::cli::render_layout;
+use rololib::prelude::*;
+use std::collections::VecDeque;
+use std::io::Read;
+use std::time::Duration;
+
+/// Input arriving in separate reads, like a pipe written in bursts
+struct Chunks(VecDeque<&'static str>);
+
+impl Read for Chunks {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let chunk = self.0.pop_front().unwrap_or("").as_bytes();
+        buf[..chunk.len()].copy_from_slice(chunk);
+        Ok(chunk.len())
+    }
+}
+
+/// Output of following `chunks` without a TTY, laid out by `words`
+fn follow_chunks(words: &[&str], chunks: &[&'static str]) -> String {
+    let args: Vec<String> = std::iter::once("rolo").chain(words.iter().copied()).map(String::from).collect();
+    let config = parse_args(&args).unwrap();
+    let follow = FollowConfig { poll_interval: Duration::ZERO, in_place: false, ..FollowConfig::default() };
+
+    let mut out = Vec::new();
+    follow_reader(&follow, Chunks(chunks.iter().copied().collect()), &mut out, |input| render_layout(&config, input)).unwrap();
+    String::from_utf8(out).unwrap()
+}
+
+fn layout(words: &[&str], input: &str) -> String {
+    let args: Vec<String> = std::iter::once("rolo").chain(words.iter().copied()).map(String::from).collect();
+    render_layout(&parse_args(&args).unwrap(), input).unwrap()
+}
+
+#[test]
+fn test_follow_config_defaults() {
+    let config = FollowConfig::default();
+    assert!(matches!(config.source, FollowSource::Stdin));
+    assert_eq!(config.poll_interval, Duration::from_millis(250));
+    assert_eq!(config.stream.max_buffer_size, StreamConfig::default().max_buffer_size);
+}
+
+#[test]
+fn test_follow_missing_file_reports_error() {
+    let config = FollowConfig {
+        source: FollowSource::File("/nonexistent/rolo-follow-test.log".into()),
+        in_place: false,
+        ..FollowConfig::default()
+    };
+
+    let result: Result<(), StreamError> = follow_input(&config, |input| Ok(input.to_string()));
+    match result {
+        Err(StreamError::IoError(msg)) => assert!(msg.contains("rolo-follow-test.log")),
+        other => panic!("expected IoError for missing file, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_follow_without_tty_writes_whole_column_frames() {
+    let words = ["columns", "--cols", "2", "--width", "40"];
+    // The partial "6" waits for its newline; an unchanged layout isn't written again
+    let output = follow_chunks(&words, &["1\n2\n3\n4\n", "5\n", "6", "\n"]);
+
+    let expected = format!(
+        "{}\n\n{}\n\n{}\n\n",
+        layout(&words, "1\n2\n3\n4\n"),
+        layout(&words, "1\n2\n3\n4\n5\n"),
+        layout(&words, "1\n2\n3\n4\n5\n6\n")
+    );
+    assert_eq!(output, expected);
+
+    // The last frame has every item exactly once
+    let last = output.trim_end().rsplit("\n\n").next().unwrap();
+    let mut items: Vec<&str> = last.split_whitespace().collect();
+    items.sort();
+    assert_eq!(items, vec!["1", "2", "3", "4", "5", "6"]);
+}
+
+#[test]
+fn test_follow_without_tty_writes_whole_table_frames() {
+    let words = ["table", "--width", "60"];
+    let output = follow_chunks(&words, &["name\tage\nann\t3\n", "bartholomew\t42\n"]);
+
+    let full = layout(&words, "name\tage\nann\t3\nbartholomew\t42\n");
+    assert_eq!(output, format!("{}\n\n{}\n\n", layout(&words, "name\tage\nann\t3\n"), full));
+
+    // Later rows line up with the header of their own frame
+    let last: Vec<&str> = full.lines().collect();
+    assert_eq!(last[0].find('|'), last[3].find('|'), "{}", full);
+}
@@ -0,0 +1,79 @@
+//! Line ending detection, normalization and output conversion feature tests
+
+use rololib::prelude::*;
+
+#[test]
+fn test_line_ending_detect() {
+    assert_eq!(LineEnding::detect("a\nb\n"), Some(LineEnding::Unix));
+    assert_eq!(LineEnding::detect("a\r\nb\r\n"), Some(LineEnding::Windows));
+    assert_eq!(LineEnding::detect("a\rb\r"), Some(LineEnding::Mac));
+    assert_eq!(LineEnding::detect("no breaks"), None);
+}
+
+#[test]
+fn test_line_ending_parse() {
+    assert_eq!(LineEnding::parse("lf"), Some(LineEnding::Unix));
+    assert_eq!(LineEnding::parse("CRLF"), Some(LineEnding::Windows));
+    assert_eq!(LineEnding::parse("cr"), Some(LineEnding::Mac));
+    assert_eq!(LineEnding::parse("unicode"), None);
+}
+
+#[test]
+fn test_normalize_line_endings() {
+    assert_eq!(normalize_line_endings("a\r\nb\r\n"), "a\nb\n");
+    assert_eq!(normalize_line_endings("a\rb\r"), "a\nb\n");
+    // Bare CR inside LF text is content (e.g. progress output), not a break
+    assert_eq!(normalize_line_endings("50%\r100%\ndone\n"), "50%\r100%\ndone\n");
+}
+
+#[test]
+fn test_apply_line_ending_translates_only_newlines() {
+    assert_eq!(apply_line_ending("a\nb\n", LineEnding::Windows), "a\r\nb\r\n");
+    assert_eq!(apply_line_ending("a\nb\n", LineEnding::Mac), "a\rb\r");
+    // A CR inside a cell is content, not a line break
+    assert_eq!(apply_line_ending("50%\r100% |\nok\n", LineEnding::Windows), "50%\r100% |\r\nok\r\n");
+    assert_eq!(apply_line_ending("50%\r100% |\r\n", LineEnding::Unix), "50%\r100% |\r\n");
+}
+
+#[test]
+fn test_output_writer_keeps_cell_carriage_returns() {
+    let config = StreamConfig { line_ending: LineEnding::Windows, ..StreamConfig::default() };
+    let mut out = OutputWriter::new(Vec::new(), &config);
+    out.write_str("a\rb | c\nd | e\n").unwrap();
+    assert_eq!(out.into_inner().unwrap(), b"a\rb | c\r\nd | e\r\n");
+}
+
+#[test]
+fn test_cr_input_is_split_into_table_rows() {
+    let unix = format_table_with_config("name\tage\nalice\t30\n", "\t", 80).unwrap();
+    let mac = "name\tage\ralice\t30\r";
+
+    // Without normalization the CRs don't end lines: everything is one row
+    let raw = StreamConfig { normalize_line_endings: false, ..StreamConfig::default() };
+    let unsplit = format_table_with_config(&prepare_text(mac, &raw), "\t", 80).unwrap();
+    assert_eq!(unsplit.lines().count(), 1, "{}", unsplit);
+
+    let normalized = format_table_with_config(&prepare_text(mac, &StreamConfig::default()), "\t", 80).unwrap();
+    assert_eq!(normalized, unix);
+}
+
+#[test]
+fn test_cr_in_last_cell_of_lf_input_is_kept() {
+    // Progress-style CRs in LF input are cell content, with or without normalization
+    let input = "task\tstatus\nbuild\t50%\r100%\n";
+    for normalize_line_endings in [true, false] {
+        let config = StreamConfig { normalize_line_endings, ..StreamConfig::default() };
+        let model = TableModel::parse(&prepare_text(input, &config), "\t");
+        assert_eq!(model.rows, vec![vec!["build", "50%\r100%"]]);
+    }
+}
+
+#[test]
+fn test_pipeline_process_applies_line_ending() {
+    let output = Pipeline::new()
+        .line_ending(LineEnding::Mac)
+        .process("x\ny\n", |stream| stream)
+        .unwrap();
+
+    assert_eq!(output, "x\ry");
+}
@@ -0,0 +1,57 @@
+//! NUL-delimited input and output feature tests
+
+use rololib::cli::render_layout;
+use rololib::prelude::*;
+
+const FIND_PRINT0: &str = "./my file.txt\0./two\nlines\0./plain\0";
+
+#[test]
+fn test_split_records_null_data() {
+    let config = StreamConfig { null_data: true, ..StreamConfig::default() };
+    assert_eq!(split_records(FIND_PRINT0, &config), vec!["./my file.txt", "./two\nlines", "./plain"]);
+
+    let lines = StreamConfig::default();
+    assert_eq!(split_records("a\nb\n", &lines), vec!["a", "b"]);
+}
+
+#[test]
+fn test_columns_split_on_nul_keep_spaces_and_newlines_in_one_item() {
+    let config = LayoutConfig { width: 60, gap: 2, padding: 1 };
+    let output = format_columns_with_delimiter(FIND_PRINT0, 3, &config, Some("\0")).unwrap();
+
+    assert_eq!(output.lines().count(), 1);
+    assert!(output.contains("./my file.txt"));
+    assert!(output.contains("./two?lines"));
+}
+
+#[test]
+fn test_list_items_are_not_split_on_newlines() {
+    let items = vec!["first\nrecord", "second"];
+    let output = format_list_items(&items, &ListConfig::default()).unwrap();
+    assert_eq!(output, "first?record\nsecond");
+}
+
+#[test]
+fn test_list_null_data_keeps_record_with_newline_as_one_item() {
+    let args: Vec<String> = ["rolo", "list", "-z"].iter().map(|arg| arg.to_string()).collect();
+    let config = parse_args(&args).unwrap();
+    assert_eq!(render_layout(&config, "a\nb\0c\0").unwrap(), "a?b\nc");
+}
+
+#[test]
+fn test_nul_output_line_ending() {
+    assert_eq!(LineEnding::parse("nul"), Some(LineEnding::Nul));
+    assert_eq!(apply_line_ending("a\nb\n", LineEnding::Nul), "a\0b\0");
+}
+
+#[test]
+fn test_staged_pipeline_null_data() {
+    let output = Pipeline::new()
+        .null_data(true)
+        .line_ending(LineEnding::Nul)
+        .stage(stages::filter_lines(|record| record.contains(' ')))
+        .process(FIND_PRINT0)
+        .unwrap();
+
+    assert_eq!(output, "./my file.txt\0");
+}
@@ -0,0 +1,67 @@
+//! Atomic file output and color policy feature tests
+
+use rololib::prelude::*;
+use std::fs;
+use std::path::PathBuf;
+
+/// Fresh scratch directory per test under the system temp dir
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("rolo-output-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn test_write_file_atomic_replaces_file_and_leaves_no_temp() {
+    let dir = scratch_dir("replace");
+    let path = dir.join("report.txt");
+    fs::write(&path, "old contents that are longer\n").unwrap();
+
+    write_file_atomic(&path, "a  b\nc  d\n", &StreamConfig::default()).unwrap();
+
+    assert_eq!(fs::read_to_string(&path).unwrap(), "a  b\nc  d\n");
+    assert_eq!(fs::read_dir(&dir).unwrap().count(), 1, "temp file left behind");
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_write_file_atomic_honors_line_ending_and_color_policy() {
+    let dir = scratch_dir("policy");
+    let path = dir.join("report.txt");
+    let colored = "\x1b[31mred\x1b[0m\nplain\n";
+
+    let config = StreamConfig { line_ending: LineEnding::Windows, color: ColorPolicy::Auto, ..StreamConfig::default() };
+    write_file_atomic(&path, colored, &config).unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap(), "red\r\nplain\r\n");
+
+    write_file_atomic(&path, colored, &StreamConfig::default()).unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap(), colored);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_write_file_atomic_missing_directory_fails_cleanly() {
+    let path = std::env::temp_dir().join("rolo-no-such-dir").join("out.txt");
+    let result = write_file_atomic(&path, "x\n", &StreamConfig::default());
+    assert!(matches!(result, Err(StreamError::IoError(_))));
+}
+
+#[test]
+fn test_color_policy() {
+    assert_eq!(ColorPolicy::parse("auto"), Some(ColorPolicy::Auto));
+    assert_eq!(ColorPolicy::parse("never"), Some(ColorPolicy::Never));
+    assert_eq!(ColorPolicy::parse("sometimes"), None);
+
+    assert!(ColorPolicy::Auto.strips_for(false));
+    assert!(!ColorPolicy::Auto.strips_for(true));
+    assert!(!ColorPolicy::Always.strips_for(false));
+    assert!(ColorPolicy::Never.strips_for(true));
+}
+
+#[test]
+fn test_strip_ansi() {
+    assert_eq!(strip_ansi("\x1b[1;32mok\x1b[0m done"), "ok done");
+    assert_eq!(strip_ansi("\x1b]8;;https://example.com\x1b\\link\x1b]8;;\x07"), "link");
+    assert_eq!(strip_ansi("plain"), "plain");
+}
@@ -0,0 +1,78 @@
+//! Typed multi-stage pipeline feature tests
+
+use rololib::prelude::*;
+use rololib::stream::stages::{self, SortOrder};
+
+const SAMPLE: &str = "name\tscore\ncarol\t9\nalice\t10\nbob\t7\n";
+
+#[test]
+fn test_table_model_parse_and_records() {
+    let model = TableModel::parse(SAMPLE, "\t");
+    assert_eq!(model.headers, Some(vec!["name".to_string(), "score".to_string()]));
+    assert_eq!(model.rows.len(), 3);
+    assert_eq!(model.column_count(), 2);
+
+    let headless = TableModel::from_records(vec![vec!["a".to_string()]], false);
+    assert!(headless.headers.is_none());
+    assert_eq!(headless.rows.len(), 1);
+}
+
+#[test]
+fn test_format_table_model_matches_text_formatting() {
+    let model = TableModel::parse(SAMPLE, "\t");
+    assert_eq!(
+        format_table_model(&model, 80).unwrap(),
+        format_table_with_config(SAMPLE, "\t", 80).unwrap()
+    );
+}
+
+#[test]
+fn test_staged_pipeline_parse_sort_filter_layout() {
+    let output = Pipeline::new()
+        .stage(stages::parse_delimited("\t"))
+        .stage(stages::to_table(true))
+        .stage(stages::filter_rows(|row| row[0] != "bob"))
+        .stage(stages::sort_rows(1, SortOrder::Descending))
+        .stage(stages::layout_table(80))
+        .process(SAMPLE)
+        .unwrap();
+
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(lines.len(), 4);
+    assert!(lines[0].starts_with("name"));
+    assert!(lines[2].starts_with("alice"), "10 sorts numerically above 9: {:?}", lines);
+    assert!(lines[3].starts_with("carol"));
+    assert!(!output.contains("bob"));
+}
+
+#[test]
+fn test_staged_pipeline_custom_typed_stages() {
+    let count = Pipeline::new()
+        .stage(stages::filter_lines(|line| line.contains('a')))
+        .stage(|lines: Lines| -> Result<usize, StreamError> { Ok(lines.len()) })
+        .run_lines(vec!["alpha".to_string(), "beta".to_string(), "xyz".to_string()])
+        .unwrap();
+
+    assert_eq!(count, 2);
+}
+
+#[test]
+fn test_staged_pipeline_honors_line_ending() {
+    let output = Pipeline::new()
+        .line_ending(LineEnding::Windows)
+        .stage(stages::layout_list(ListConfig::default()))
+        .process("one\ntwo")
+        .unwrap();
+
+    assert_eq!(output, "one\r\ntwo\r\n");
+}
+
+#[test]
+fn test_staged_pipeline_layout_errors_become_stream_errors() {
+    let config = LayoutConfig { width: 10, gap: 10, padding: 1 };
+    let result = Pipeline::new()
+        .stage(stages::layout_columns(3, config))
+        .process("a\nb\nc");
+
+    assert!(matches!(result, Err(StreamError::StageFailed(_))));
+}
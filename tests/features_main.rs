@@ -8,10 +8,70 @@ mod width_features;
 #[path = "features/cli_features.rs"]
 mod cli_features;
 
+#[path = "features/cli_config_features.rs"]
+mod cli_config_features;
+
+#[path = "features/config_features.rs"]
+mod config_features;
+
+#[path = "features/completion_features.rs"]
+mod completion_features;
+
+#[path = "features/help_features.rs"]
+mod help_features;
+
+#[path = "features/explain_features.rs"]
+mod explain_features;
+
+#[path = "features/error_features.rs"]
+mod error_features;
+
+#[path = "features/format_features.rs"]
+mod format_features;
+
 #[path = "features/layout/list_features.rs"]
 mod list_features;
 
+#[path = "features/stream/follow_features.rs"]
+mod follow_features;
+
+#[path = "features/stream/command_features.rs"]
+mod command_features;
+
+#[path = "features/stream/pipeline_features.rs"]
+mod pipeline_features;
+
+#[path = "features/stream/line_ending_features.rs"]
+mod line_ending_features;
+
+#[path = "features/stream/null_data_features.rs"]
+mod null_data_features;
+
+#[path = "features/stream/encoding_features.rs"]
+mod encoding_features;
+
+#[path = "features/stream/binary_features.rs"]
+mod binary_features;
+
+#[path = "features/stream/output_features.rs"]
+mod output_features;
+
 // Re-export feature tests
 pub use width_features::*;
 pub use cli_features::*;
-pub use list_features::*;
\ No newline at end of file
+pub use cli_config_features::*;
+pub use config_features::*;
+pub use completion_features::*;
+pub use help_features::*;
+pub use explain_features::*;
+pub use error_features::*;
+pub use format_features::*;
+pub use list_features::*;
+pub use follow_features::*;
+pub use command_features::*;
+pub use pipeline_features::*;
+pub use line_ending_features::*;
+pub use null_data_features::*;
+pub use encoding_features::*;
+pub use binary_features::*;
+pub use output_features::*;
\ No newline at end of file